@@ -16,9 +16,13 @@ fn fixture(depth: usize) -> String {
     )
 }
 
+/// Mixed-feature template (conditionals, loops, match, composition).
+const DASHBOARD: &str = include_str!("fixtures/Dashboard.ruitl");
+
 fn bench_codegen(c: &mut Criterion) {
     let shallow = parse_str(&fixture(2)).unwrap();
     let deep = parse_str(&fixture(30)).unwrap();
+    let dashboard = parse_str(DASHBOARD).unwrap();
 
     let mut group = c.benchmark_group("codegen");
     group.bench_function("shallow", |b| {
@@ -33,6 +37,12 @@ fn bench_codegen(c: &mut Criterion) {
             gen.generate().unwrap()
         })
     });
+    group.bench_function("dashboard", |b| {
+        b.iter(|| {
+            let mut gen = CodeGenerator::new(dashboard.clone());
+            gen.generate().unwrap()
+        })
+    });
     group.finish();
 }
 
@@ -25,6 +25,9 @@ fn fixture(list_items: usize) -> String {
     s
 }
 
+/// Mixed-feature template (conditionals, loops, match, composition).
+const DASHBOARD: &str = include_str!("fixtures/Dashboard.ruitl");
+
 fn bench_parse(c: &mut Criterion) {
     let small = fixture(4);
     let medium = fixture(50);
@@ -34,6 +37,7 @@ fn bench_parse(c: &mut Criterion) {
     group.bench_function("small", |b| b.iter(|| parse_str(&small).unwrap()));
     group.bench_function("medium", |b| b.iter(|| parse_str(&medium).unwrap()));
     group.bench_function("large", |b| b.iter(|| parse_str(&large).unwrap()));
+    group.bench_function("dashboard", |b| b.iter(|| parse_str(DASHBOARD).unwrap()));
     group.finish();
 }
 
@@ -44,8 +44,22 @@ fn main() {
         println!("cargo:rerun-if-changed={}", dir.display());
         emit_rerun_for_ruitl_files(dir);
 
-        match ruitl_compiler::compile_dir_sibling(dir) {
-            Ok(paths) => compiled.extend(paths),
+        match ruitl_compiler::compile_dir_sibling_report(dir) {
+            Ok(report) => {
+                for path in &report.skipped {
+                    println!(
+                        "cargo:warning=Skipped {}: no component or template definitions",
+                        path.display()
+                    );
+                }
+                for warning in &report.warnings {
+                    // Cargo shows one line per `cargo:warning`.
+                    for line in warning.lines() {
+                        println!("cargo:warning={}", line);
+                    }
+                }
+                compiled.extend(report.outputs);
+            }
             Err(e) => errors.push(format!("{}: {}", dir.display(), e)),
         }
     }
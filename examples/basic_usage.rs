@@ -42,7 +42,7 @@ impl Component for Hello {
     ) -> ruitl::error::Result<Html> {
         use ruitl::html::*;
         Ok(Html::Element(div().class("greeting").child(Html::Element(
-            h1().text(&format!("Hello, {}!", props.name)),
+            h1().text(format!("Hello, {}!", props.name)),
         ))))
     }
 }
@@ -59,7 +59,7 @@ impl ComponentProps for ButtonProps {
     fn validate(&self) -> ruitl::error::Result<()> {
         let valid_variants = vec!["primary", "secondary", "success", "danger"];
         if !valid_variants.contains(&self.variant.as_str()) {
-            return Err(RuitlError::validation(&format!(
+            return Err(RuitlError::validation(format!(
                 "Invalid variant '{}'. Must be one of: {:?}",
                 self.variant, valid_variants
             )));
@@ -144,7 +144,7 @@ impl Component for UserCard {
         let avatar_elem = if let Some(ref url) = props.avatar_url {
             img()
                 .attr("src", url)
-                .attr("alt", &format!("{}'s avatar", props.name))
+                .attr("alt", format!("{}'s avatar", props.name))
                 .class("avatar")
         } else {
             div().class("avatar-placeholder").text(&initials)
@@ -229,7 +229,7 @@ fn main() -> Result<()> {
 
     let user_card = UserCard;
 
-    let users = vec![
+    let users = [
         UserCardProps {
             name: "Alice Johnson".to_string(),
             email: "alice@example.com".to_string(),
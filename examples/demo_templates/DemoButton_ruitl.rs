@@ -1,4 +1,11 @@
-// ruitl-hash: 602148dc4f4775bdc2f44fe3d28d95e3
+// ruitl-hash: cbef64f1798ecc83c0b9a7ba441b5a97
+// ruitl-components: DemoButton
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -12,6 +19,49 @@ impl ComponentProps for DemoButtonProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl DemoButtonProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> DemoButtonPropsBuilder {
+        DemoButtonPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct DemoButtonPropsBuilder {
+    text: Option<String>,
+    variant: Option<String>,
+    href: Option<String>,
+}
+#[allow(dead_code)]
+impl DemoButtonPropsBuilder {
+    pub fn text(mut self, value: impl Into<String>) -> Self {
+        self.text = Some(value.into());
+        self
+    }
+    pub fn variant(mut self, value: impl Into<String>) -> Self {
+        self.variant = Some(value.into());
+        self
+    }
+    pub fn href(mut self, value: impl Into<String>) -> Self {
+        self.href = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<DemoButtonProps> {
+        let props = DemoButtonProps {
+            text: self
+                .text
+                .ok_or_else(|| RuitlError::validation("missing required prop `text`"))?,
+            variant: self
+                .variant
+                .ok_or_else(|| RuitlError::validation("missing required prop `variant`"))?,
+            href: self.href,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct DemoButton;
 impl Component for DemoButton {
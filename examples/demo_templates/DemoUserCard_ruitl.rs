@@ -1,4 +1,11 @@
-// ruitl-hash: 5082fe385bf41df61895c5f2cfd37ed5
+// ruitl-hash: 6b8f15543aeb3caa0db5838d70901225
+// ruitl-components: DemoUserCard
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -12,6 +19,51 @@ impl ComponentProps for DemoUserCardProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl DemoUserCardProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> DemoUserCardPropsBuilder {
+        DemoUserCardPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct DemoUserCardPropsBuilder {
+    name: Option<String>,
+    email: Option<String>,
+    role: Option<String>,
+}
+#[allow(dead_code)]
+impl DemoUserCardPropsBuilder {
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+    pub fn email(mut self, value: impl Into<String>) -> Self {
+        self.email = Some(value.into());
+        self
+    }
+    pub fn role(mut self, value: impl Into<String>) -> Self {
+        self.role = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<DemoUserCardProps> {
+        let props = DemoUserCardProps {
+            name: self
+                .name
+                .ok_or_else(|| RuitlError::validation("missing required prop `name`"))?,
+            email: self
+                .email
+                .ok_or_else(|| RuitlError::validation("missing required prop `email`"))?,
+            role: self
+                .role
+                .ok_or_else(|| RuitlError::validation("missing required prop `role`"))?,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct DemoUserCard;
 impl Component for DemoUserCard {
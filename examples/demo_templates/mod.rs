@@ -5,3 +5,10 @@
 
 #[allow(unused_imports)] pub use DemoButton_ruitl::*;
 #[allow(unused_imports)] pub use DemoUserCard_ruitl::*;
+
+/// Register every component compiled from this directory, keyed by name.
+#[allow(dead_code, unused_variables)]
+pub fn register_all(renderer: &mut ruitl::component::ComponentRenderer) {
+    renderer.register("DemoButton", DemoButton_ruitl::DemoButton);
+    renderer.register("DemoUserCard", DemoUserCard_ruitl::DemoUserCard);
+}
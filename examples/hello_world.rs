@@ -29,7 +29,7 @@ impl Component for Greeting {
         Ok(Html::Element(
             ruitl::html::div()
                 .child(Html::Element(
-                    ruitl::html::h1().text(&format!("{}, {}!", message, props.name)),
+                    ruitl::html::h1().text(format!("{}, {}!", message, props.name)),
                 ))
                 .child(Html::Element(ruitl::html::p().text("Welcome to RUITL!"))),
         ))
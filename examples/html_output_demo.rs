@@ -135,7 +135,7 @@ impl ComponentProps for ButtonProps {
     fn validate(&self) -> ruitl::error::Result<()> {
         let valid_variants = vec!["primary", "secondary", "success", "danger"];
         if !valid_variants.contains(&self.variant.as_str()) {
-            return Err(RuitlError::validation(&format!(
+            return Err(RuitlError::validation(format!(
                 "Invalid variant '{}'. Must be one of: {:?}",
                 self.variant, valid_variants
             )));
@@ -223,17 +223,17 @@ impl Component for UserCard {
         Ok(Html::Element(
             div()
                 .class("card")
-                .child(Html::Element(h3().text(&format!("👤 {}", props.name))))
-                .child(Html::Element(p().text(&format!("📧 {}", props.email))))
-                .child(Html::Element(p().text(&format!("🔖 Role: {}", props.role))))
+                .child(Html::Element(h3().text(format!("👤 {}", props.name))))
+                .child(Html::Element(p().text(format!("📧 {}", props.email))))
+                .child(Html::Element(p().text(format!("🔖 Role: {}", props.role))))
                 .child(Html::Element(
                     p().child(Html::Element(
                         span()
                             .attr(
                                 "style",
-                                &format!("color: {}; font-weight: bold;", status_color),
+                                format!("color: {}; font-weight: bold;", status_color),
                             )
-                            .text(&format!("● Status: {}", status_text)),
+                            .text(format!("● Status: {}", status_text)),
                     )),
                 )),
         ))
@@ -475,28 +475,27 @@ fn main() -> ruitl::error::Result<()> {
     save_html_file("composition_demo.html", &composition_demo)?;
 
     // Generate an index page
-    let index_html = format!(
-        "<!DOCTYPE html>
+    let index_html = "<!DOCTYPE html>
         <html>
         <head>
             <title>RUITL HTML Output Demos</title>
             <style>
-                body {{
+                body {
                     font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
                     line-height: 1.6; margin: 0; padding: 40px; background: #f5f5f5;
-                }}
-                .container {{
+                }
+                .container {
                     max-width: 600px; margin: 0 auto; background: white;
                     padding: 40px; border-radius: 8px; box-shadow: 0 2px 10px rgba(0,0,0,0.1);
-                }}
-                .demo-link {{
+                }
+                .demo-link {
                     display: block; background: #007bff; color: white; padding: 15px 20px;
                     text-decoration: none; margin: 10px 0; border-radius: 6px;
                     transition: background 0.2s;
-                }}
-                .demo-link:hover {{ background: #0056b3; }}
-                h1 {{ color: #343a40; }}
-                p {{ color: #6c757d; }}
+                }
+                .demo-link:hover { background: #0056b3; }
+                h1 { color: #343a40; }
+                p { color: #6c757d; }
             </style>
         </head>
         <body>
@@ -535,8 +534,7 @@ fn main() -> ruitl::error::Result<()> {
                 </p>
             </div>
         </body>
-        </html>"
-    );
+        </html>".to_string();
 
     save_html_file("index.html", &index_html)?;
 
@@ -16,7 +16,6 @@ use hyper::{Body, Method, Request, Response, Server, StatusCode};
 use ruitl::prelude::*;
 use std::convert::Infallible;
 use std::net::SocketAddr;
-use tokio;
 
 // Pull in the generated siblings for the demo_templates directory.
 #[path = "demo_templates/mod.rs"]
@@ -90,7 +89,7 @@ impl ComponentProps for ButtonProps {
     fn validate(&self) -> ruitl::error::Result<()> {
         let valid_variants = vec!["primary", "secondary", "success", "danger"];
         if !valid_variants.contains(&self.variant.as_str()) {
-            return Err(RuitlError::validation(&format!(
+            return Err(RuitlError::validation(format!(
                 "Invalid variant '{}'. Must be one of: {:?}",
                 self.variant, valid_variants
             )));
@@ -115,13 +114,13 @@ impl Component for Button {
         if let Some(href) = &props.href {
             Ok(Html::Element(
                 a().attr("href", href)
-                    .class(&format!("button btn-{}", props.variant))
+                    .class(format!("button btn-{}", props.variant))
                     .text(&props.text),
             ))
         } else {
             Ok(Html::Element(
                 button()
-                    .class(&format!("button btn-{}", props.variant))
+                    .class(format!("button btn-{}", props.variant))
                     .attr("type", "button")
                     .text(&props.text),
             ))
@@ -163,9 +162,9 @@ impl Component for UserCard {
         Ok(Html::Element(
             div()
                 .class("card")
-                .child(Html::Element(h3().text(&format!("User: {}", props.name))))
-                .child(Html::Element(p().text(&format!("Email: {}", props.email))))
-                .child(Html::Element(p().text(&format!("Role: {}", props.role)))),
+                .child(Html::Element(h3().text(format!("User: {}", props.name))))
+                .child(Html::Element(p().text(format!("Email: {}", props.email))))
+                .child(Html::Element(p().text(format!("Role: {}", props.role)))),
         ))
     }
 }
@@ -23,9 +23,9 @@ fn big_page() -> Html {
     for i in 0..100 {
         children.push(Html::Element(
             HtmlElement::new("section")
-                .attr("data-i", &format!("{i}"))
+                .attr("data-i", format!("{i}"))
                 .child(Html::Element(
-                    HtmlElement::new("h2").text(&format!("Section {i}")),
+                    HtmlElement::new("h2").text(format!("Section {i}")),
                 ))
                 .child(Html::Element(HtmlElement::new("p").text(
                     "Lorem ipsum dolor sit amet, consectetur adipiscing elit.",
@@ -8,6 +8,10 @@
 //!
 //! Run with: cargo run --example template_compiler_demo
 
+// Template listings are printed through `"{}"` so the raw strings stay
+// verbatim (no brace escaping).
+#![allow(clippy::print_literal)]
+
 use ruitl::prelude::*;
 
 fn main() -> Result<()> {
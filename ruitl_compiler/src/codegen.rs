@@ -3,12 +3,14 @@
 //! Converts parsed .ruitl templates into optimized Rust code that uses the RUITL runtime library.
 
 use crate::error::{CompileError, Result};
+use crate::markup::{escape_html, is_void_element};
 use crate::parser::{
-    Attribute, AttributeValue, ComponentDef, ImportDef, MatchArm, PropValue, RuitlFile,
-    TemplateAst, TemplateDef,
+    Attribute, AttributeValue, ComponentDef, ImportDef, MatchArm, PropAnnotation, PropDef,
+    PropValue, RuitlFile, TemplateAst, TemplateDef,
 };
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote, ToTokens};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use syn::{parse_str, Expr, Type};
 
@@ -99,11 +101,34 @@ fn scan_idents(src: &str, out: &mut std::collections::HashSet<String>) {
     }
 }
 
+/// What to do with a template `match` that has no catch-all (`_` or binding)
+/// arm. Rust would reject a non-exhaustive one, but only in the generated
+/// file, far from the template that caused it. Matches on enum variant
+/// paths (`Role::Admin`) are left to rustc, since listing every variant is
+/// exhaustive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchDefaultArm {
+    /// Emit the match as written
+    Allow,
+    /// Record a warning (see [`CodeGenerator::warnings`]) and carry on
+    #[default]
+    Warn,
+    /// Fail code generation
+    Deny,
+}
+
 /// Code generator for converting RUITL templates to Rust code
 pub struct CodeGenerator {
     file: RuitlFile,
     generated_components: HashMap<String, TokenStream>,
     generated_imports: Vec<TokenStream>,
+    match_default_arm: MatchDefaultArm,
+    fold_static: bool,
+    lint_inline_handlers: bool,
+    warnings: RefCell<Vec<String>>,
+    /// Static element subtrees that occur more than once in the template
+    /// being generated, each paired with the `let` it is bound to.
+    shared_statics: RefCell<Vec<(TemplateAst, Ident)>>,
 }
 
 impl CodeGenerator {
@@ -113,9 +138,42 @@ impl CodeGenerator {
             file,
             generated_components: HashMap::new(),
             generated_imports: Vec::new(),
+            match_default_arm: MatchDefaultArm::default(),
+            fold_static: false,
+            lint_inline_handlers: false,
+            warnings: RefCell::new(Vec::new()),
+            shared_statics: RefCell::new(Vec::new()),
         }
     }
 
+    /// Choose how matches without a catch-all arm are reported
+    pub fn with_match_default_arm(mut self, mode: MatchDefaultArm) -> Self {
+        self.match_default_arm = mode;
+        self
+    }
+
+    /// Pre-render element subtrees with no expressions, control flow or
+    /// component calls into one `Html::Raw` literal each (see
+    /// [`CompileOptions::fold_static`](crate::CompileOptions::fold_static)).
+    /// Off by default.
+    pub fn with_fold_static(mut self, fold: bool) -> Self {
+        self.fold_static = fold;
+        self
+    }
+
+    /// Record a warning (see [`CodeGenerator::warnings`]) for every inline
+    /// event handler attribute (`onclick`, `onsubmit`, ...) in a template.
+    /// Off by default.
+    pub fn with_lint_inline_handlers(mut self, lint: bool) -> Self {
+        self.lint_inline_handlers = lint;
+        self
+    }
+
+    /// Non-fatal diagnostics collected by the last [`generate`](Self::generate)
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+
     /// Generate complete Rust code for the entire file
     pub fn generate(&mut self) -> Result<TokenStream> {
         // Check templates for undefined `@Component` references, unknown
@@ -149,7 +207,17 @@ impl CodeGenerator {
             .filter_map(|c| self.generated_components.get(&c.name))
             .collect();
 
+        // Bodies route every interpolation through `format!` and iterate
+        // loop sources with `.into_iter()` whatever their type; clippy flags
+        // those patterns in the generated file, not in the template.
         Ok(quote! {
+            #![allow(
+                clippy::useless_format,
+                clippy::format_in_format_args,
+                clippy::into_iter_on_ref,
+                clippy::needless_borrows_for_generic_args
+            )]
+
             use ruitl::prelude::*;
             use ruitl::html::*;
 
@@ -273,16 +341,35 @@ impl CodeGenerator {
                 quote! { #field_type }
             };
 
+            let docs = prop_doc_lines(prop);
             fields.push(quote! {
+                #(#[doc = #docs])*
                 pub #field_name: #field_type
             });
 
-            // Add validation if needed
-            if !prop.optional {
-                field_validations.push(quote! {
-                    // Non-optional field validation could go here
-                });
+            let checks = prop
+                .annotations
+                .iter()
+                .map(|a| annotation_check(prop, a))
+                .collect::<Result<Vec<_>>>()?;
+            let checks: Vec<TokenStream> = checks.into_iter().flatten().collect();
+            if checks.is_empty() {
+                continue;
             }
+            field_validations.push(if prop.optional {
+                quote! {
+                    if let Some(value) = &self.#field_name {
+                        #(#checks)*
+                    }
+                }
+            } else {
+                quote! {
+                    {
+                        let value = &self.#field_name;
+                        #(#checks)*
+                    }
+                }
+            });
         }
 
         if needs_children {
@@ -291,6 +378,20 @@ impl CodeGenerator {
             });
         }
 
+        // `@min(0)` on an unsigned prop is a tautology the compiler flags,
+        // and `@min_len(1)` reads as `len() < 1` rather than `is_empty()`.
+        let validate_allow = if field_validations.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                #[allow(
+                    unused_comparisons,
+                    clippy::absurd_extreme_comparisons,
+                    clippy::len_zero
+                )]
+            }
+        };
+
         let (struct_decl, impl_decl) = if component.generics.is_empty() {
             (quote! { pub struct #props_name }, quote! { impl ComponentProps for #props_name })
         } else {
@@ -304,6 +405,14 @@ impl CodeGenerator {
             )
         };
 
+        // Builders and `Default` are only emitted for non-generic props;
+        // generic ones are built with a struct literal.
+        let construction = if component.generics.is_empty() {
+            self.generate_props_construction(component, needs_children)?
+        } else {
+            quote! {}
+        };
+
         Ok(quote! {
             #[derive(Debug, Clone)]
             #struct_decl {
@@ -311,11 +420,125 @@ impl CodeGenerator {
             }
 
             #impl_decl {
+                #validate_allow
                 fn validate(&self) -> Result<()> {
                     #(#field_validations)*
                     Ok(())
                 }
             }
+
+            #construction
+        })
+    }
+
+    /// `XProps::builder()` plus its `XPropsBuilder`, filling unset props
+    /// from their `= default` and erroring in `build()` on a missing
+    /// required prop. When no prop is required, also a `Default` impl.
+    fn generate_props_construction(
+        &self,
+        component: &ComponentDef,
+        needs_children: bool,
+    ) -> Result<TokenStream> {
+        let props_name = format_ident!("{}Props", component.name);
+        let builder_name = format_ident!("{}PropsBuilder", component.name);
+
+        let mut builder_fields = Vec::new();
+        let mut setters = Vec::new();
+        let mut build_fields = Vec::new();
+        let mut default_fields = Vec::new();
+        let mut all_defaulted = true;
+
+        for prop in &component.props {
+            let field_name = format_ident!("{}", prop.name);
+            let field_type: Type = parse_str(&prop.prop_type).map_err(|e| {
+                CompileError::codegen(format!("Invalid type '{}': {}", prop.prop_type, e))
+            })?;
+
+            builder_fields.push(quote! { #field_name: Option<#field_type> });
+            setters.push(quote! {
+                pub fn #field_name(mut self, value: impl Into<#field_type>) -> Self {
+                    self.#field_name = Some(value.into());
+                    self
+                }
+            });
+
+            if prop.optional {
+                build_fields.push(quote! { #field_name: self.#field_name });
+                default_fields.push(quote! { #field_name: None });
+            } else if let Some((default, is_literal)) = prop_default_expr(prop)? {
+                build_fields.push(if is_literal {
+                    quote! { #field_name: self.#field_name.unwrap_or(#default) }
+                } else {
+                    quote! { #field_name: self.#field_name.unwrap_or_else(|| #default) }
+                });
+                default_fields.push(quote! { #field_name: #default });
+            } else {
+                let missing = format!("missing required prop `{}`", prop.name);
+                build_fields.push(quote! {
+                    #field_name: self.#field_name.ok_or_else(|| RuitlError::validation(#missing))?
+                });
+                all_defaulted = false;
+            }
+        }
+
+        if needs_children {
+            builder_fields.push(quote! { children: Option<Html> });
+            setters.push(quote! {
+                pub fn children(mut self, value: impl Into<Html>) -> Self {
+                    self.children = Some(value.into());
+                    self
+                }
+            });
+            build_fields.push(quote! { children: self.children.unwrap_or(Html::Empty) });
+            default_fields.push(quote! { children: Html::Empty });
+        }
+
+        let default_impl = if all_defaulted {
+            quote! {
+                impl Default for #props_name {
+                    fn default() -> Self {
+                        Self {
+                            #(#default_fields),*
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // `dead_code` is allowed so binaries that construct props with struct
+        // literals don't warn about the unused builder.
+        Ok(quote! {
+            #[allow(dead_code)]
+            impl #props_name {
+                /// Start building props; unset fields take their declared defaults.
+                pub fn builder() -> #builder_name {
+                    #builder_name::default()
+                }
+            }
+
+            #[allow(dead_code)]
+            #[derive(Debug, Clone, Default)]
+            pub struct #builder_name {
+                #(#builder_fields),*
+            }
+
+            #[allow(dead_code)]
+            impl #builder_name {
+                #(#setters)*
+
+                /// Fill in defaults, check required props are set, then `validate()`.
+                pub fn build(self) -> Result<#props_name> {
+                    let props = #props_name {
+                        #(#build_fields),*
+                    };
+                    props.validate()?;
+                    Ok(props)
+                }
+            }
+
+            #default_impl
         })
     }
 
@@ -349,18 +572,31 @@ impl CodeGenerator {
         // we only emit `let foo = &props.foo` for props that the body actually
         // uses. Unused bindings would trigger `unused_variables` warnings in
         // downstream crates.
-        let referenced = Self::collect_referenced_idents(&template.body);
+        let mut referenced = Self::collect_referenced_idents(&template.body);
+
+        // Defaulted template params bind themselves; keep the plain prop
+        // binding from shadowing them.
+        let default_bindings = Self::generate_default_bindings(template, component, &referenced)?;
+        for param in template.params.iter().filter(|p| p.default_value.is_some()) {
+            referenced.remove(&param.name);
+        }
 
         // Generate prop bindings for local access
         let prop_bindings = self.generate_prop_bindings(component, &referenced)?;
 
         // Generate the render method body
-        let render_body = self.generate_ast_code(&template.body)?;
-
-        // Determine whether the body actually references `context` (only true
-        // when composing child components via `@Component(...)` syntax). If
-        // not, emit the parameter as `_context` to avoid unused-variable warnings.
-        let context_ident = if Self::template_uses_context(&template.body) {
+        let shared_statics = self.share_repeated_statics(&template.body)?;
+        let render_body = self.generate_ast_code(&template.body);
+        self.shared_statics.borrow_mut().clear();
+        let render_body = render_body?;
+
+        // Determine whether the body actually references `context` — either
+        // by composing child components via `@Component(...)` syntax or by
+        // naming it in an expression (`context.feature("x")`). If not, emit
+        // the parameter as `_context` to avoid unused-variable warnings.
+        let context_ident = if Self::template_uses_context(&template.body)
+            || referenced.contains("context")
+        {
             format_ident!("context")
         } else {
             format_ident!("_context")
@@ -382,6 +618,8 @@ impl CodeGenerator {
                     #[allow(unused_variables)]
                     fn render(&self, props: &Self::Props, #context_ident: &ComponentContext) -> Result<Html> {
                         #prop_bindings
+                        #default_bindings
+                        #shared_statics
                         Ok(#render_body)
                     }
                 }
@@ -396,6 +634,8 @@ impl CodeGenerator {
                     #[allow(unused_variables)]
                     fn render(&self, props: &Self::Props, #context_ident: &ComponentContext) -> Result<Html> {
                         #prop_bindings
+                        #default_bindings
+                        #shared_statics
                         Ok(#render_body)
                     }
                 }
@@ -428,7 +668,26 @@ impl CodeGenerator {
                 attributes,
                 children,
                 self_closing,
-            } => self.generate_element_code(tag, attributes, children, *self_closing),
+            } => {
+                if let Some((_, ident)) = self
+                    .shared_statics
+                    .borrow()
+                    .iter()
+                    .find(|(shared, _)| shared == ast)
+                {
+                    return Ok(quote! { #ident.clone() });
+                }
+                // Pure static markup renders identically on every call, so
+                // bake it into one pre-escaped literal instead of building
+                // the element tree at runtime. Built from parsed elements, so
+                // it skips `Html::raw`'s balance check.
+                if self.fold_static {
+                    if let Some(html) = static_html(ast) {
+                        return Ok(quote! { Html::Raw(#html.to_string()) });
+                    }
+                }
+                self.generate_element_code(tag, attributes, children, *self_closing)
+            }
 
             TemplateAst::Text(text) => {
                 if text.trim().is_empty() {
@@ -459,6 +718,30 @@ impl CodeGenerator {
                 Ok(quote! { Html::raw(format!("{}", #expr)) })
             }
 
+            TemplateAst::Debug(expr) => {
+                // `cfg!` rather than `#[cfg]` so the expression is still
+                // type-checked in release; the branch folds away there.
+                let transformed_expr = self.transform_variable_access(expr);
+                let expr: Expr = parse_str(&transformed_expr).map_err(|e| {
+                    CompileError::codegen(format!(
+                        "Invalid @debug expression '{}': {}",
+                        transformed_expr, e
+                    ))
+                })?;
+                // `Html::Raw` directly: the comment is our own markup, not
+                // user input for `Html::raw`'s balance check.
+                Ok(quote! {
+                    if cfg!(debug_assertions) {
+                        Html::Raw(format!(
+                            "<!-- @debug: {} -->",
+                            escape_comment(&format!("{:?}", #expr))
+                        ))
+                    } else {
+                        Html::Empty
+                    }
+                })
+            }
+
             TemplateAst::If {
                 condition,
                 then_branch,
@@ -500,6 +783,11 @@ impl CodeGenerator {
             }
 
             TemplateAst::Raw(html) => Ok(quote! { Html::raw(#html) }),
+
+            TemplateAst::Comment(text) => {
+                let comment = format!("<!--{}-->", text);
+                Ok(quote! { Html::Raw(#comment.to_string()) })
+            }
         }
     }
 
@@ -520,8 +808,12 @@ impl CodeGenerator {
             quote! { HtmlElement::new(#tag_name) }
         };
 
-        // Add attributes
-        for attr in attributes {
+        // Add attributes. `class:NAME` directives go last so they merge
+        // into whatever `class` attribute the element already has.
+        let (toggles, plain): (Vec<&Attribute>, Vec<&Attribute>) = attributes
+            .iter()
+            .partition(|a| matches!(a.value, AttributeValue::ClassToggle(_)));
+        for attr in plain.into_iter().chain(toggles) {
             let attr_code = self.generate_attribute_code(attr)?;
             element_code = quote! { #element_code.#attr_code };
         }
@@ -578,9 +870,9 @@ impl CodeGenerator {
                 ];
 
                 if boolean_attrs.contains(&attr_name.as_str()) {
-                    // For boolean attributes, use attr_if
+                    // Boolean attributes render bare (`disabled`), or not at all
                     Ok(quote! {
-                        attr_if(#attr_name, #condition, #attr_name)
+                        bool_attr_if(#attr_name, #condition)
                     })
                 } else {
                     // For Option attributes, use attr_optional
@@ -589,6 +881,16 @@ impl CodeGenerator {
                     })
                 }
             }
+
+            AttributeValue::ClassToggle(condition) => {
+                let condition: Expr = parse_str(condition).map_err(|e| {
+                    CompileError::codegen(format!(
+                        "Invalid class directive condition '{}': {}",
+                        condition, e
+                    ))
+                })?;
+                Ok(quote! { class_if(#attr_name, #condition) })
+            }
         }
     }
 
@@ -611,6 +913,15 @@ impl CodeGenerator {
 
         if let Some(else_branch) = else_branch {
             let else_code = self.generate_ast_code(else_branch)?;
+            if matches!(**else_branch, TemplateAst::If { .. }) {
+                // `else_code` is itself an `if … else …` expression, so the
+                // chain reads as `else if` rather than `else { if … }`.
+                return Ok(quote! {
+                    if #condition {
+                        #then_code
+                    } else #else_code
+                });
+            }
             Ok(quote! {
                 if #condition {
                     #then_code
@@ -653,14 +964,28 @@ impl CodeGenerator {
             ))
         })?;
 
+        // `0..n` and other low-precedence iterables need parentheses before
+        // `.into_iter()`, or it binds to the last operand (`0..n.into_iter()`).
+        let iterable = match iterable {
+            Expr::Range(_)
+            | Expr::Binary(_)
+            | Expr::Unary(_)
+            | Expr::Reference(_)
+            | Expr::Cast(_) => quote! { (#iterable) },
+            other => quote! { #other },
+        };
+
         let body_code = self.generate_ast_code(body)?;
 
+        // Each iteration yields a `Result` so `?` inside the loop body (a
+        // fallible helper, or a nested `@Component` call) propagates out of
+        // `render` instead of failing to compile inside the closure.
         Ok(quote! {
             Html::fragment(
                 #iterable
                     .into_iter()
-                    .map(|#var_pat| #body_code)
-                    .collect::<Vec<_>>()
+                    .map(|#var_pat| Ok(#body_code))
+                    .collect::<Result<Vec<_>>>()?
             )
         })
     }
@@ -699,6 +1024,34 @@ impl CodeGenerator {
         })
     }
 
+    /// Bind each static element subtree that occurs more than once in `body`
+    /// (say, the same markup in both branches of an `if`) to one `let`, and
+    /// register it so `generate_ast_code` emits a clone of the binding at
+    /// every site instead of rebuilding the tree.
+    fn share_repeated_statics(&self, body: &TemplateAst) -> Result<TokenStream> {
+        let mut subtrees = Vec::new();
+        collect_static_subtrees(body, &mut subtrees);
+
+        let mut repeated: Vec<&TemplateAst> = Vec::new();
+        for (i, subtree) in subtrees.iter().enumerate() {
+            if !repeated.contains(subtree) && subtrees[i + 1..].contains(subtree) {
+                repeated.push(subtree);
+            }
+        }
+
+        let mut bindings = Vec::new();
+        let mut shared = Vec::new();
+        for (i, subtree) in repeated.into_iter().enumerate() {
+            let ident = format_ident!("__ruitl_static_{}", i);
+            let code = self.generate_ast_code(subtree)?;
+            bindings.push(quote! { let #ident: Html = #code; });
+            shared.push((subtree.clone(), ident));
+        }
+        *self.shared_statics.borrow_mut() = shared;
+
+        Ok(quote! { #(#bindings)* })
+    }
+
     /// Walk a template AST and return true if any node invokes a child component
     /// (via `@Component(...)` syntax). Such invocations thread `context` through,
     /// so the render method needs the `context` parameter to be named — otherwise
@@ -728,7 +1081,9 @@ impl CodeGenerator {
             TemplateAst::Text(_)
             | TemplateAst::Expression(_)
             | TemplateAst::RawExpression(_)
+            | TemplateAst::Debug(_)
             | TemplateAst::Raw(_)
+            | TemplateAst::Comment(_)
             | TemplateAst::Children => false,
         }
     }
@@ -745,10 +1100,10 @@ impl CodeGenerator {
 
     fn collect_idents_rec(ast: &TemplateAst, out: &mut std::collections::HashSet<String>) {
         match ast {
-            TemplateAst::Text(_) | TemplateAst::Raw(_) => {}
-            TemplateAst::Expression(expr) | TemplateAst::RawExpression(expr) => {
-                scan_idents(expr, out)
-            }
+            TemplateAst::Text(_) | TemplateAst::Raw(_) | TemplateAst::Comment(_) => {}
+            TemplateAst::Expression(expr)
+            | TemplateAst::RawExpression(expr)
+            | TemplateAst::Debug(expr) => scan_idents(expr, out),
             TemplateAst::Element {
                 attributes,
                 children,
@@ -757,7 +1112,9 @@ impl CodeGenerator {
                 for attr in attributes {
                     match &attr.value {
                         AttributeValue::Static(_) => {}
-                        AttributeValue::Expression(e) | AttributeValue::Conditional(e) => {
+                        AttributeValue::Expression(e)
+                        | AttributeValue::Conditional(e)
+                        | AttributeValue::ClassToggle(e) => {
                             scan_idents(e, out);
                         }
                     }
@@ -850,6 +1207,63 @@ impl CodeGenerator {
         })
     }
 
+    /// Bindings for template params declared `name: Type = expr`. An
+    /// optional prop of the same name falls back to `expr` when `None`; with
+    /// no such prop, `expr` is a template-local value. A default on a
+    /// required prop could never apply, so it is rejected.
+    fn generate_default_bindings(
+        template: &TemplateDef,
+        component: &ComponentDef,
+        referenced: &std::collections::HashSet<String>,
+    ) -> Result<TokenStream> {
+        let mut bindings = Vec::new();
+
+        for param in &template.params {
+            let Some(default) = &param.default_value else {
+                continue;
+            };
+            if !referenced.contains(&param.name) {
+                continue;
+            }
+
+            let name = format_ident!("{}", param.name);
+            let param_type: Type = parse_str(&param.param_type).map_err(|e| {
+                CompileError::codegen(format!("Invalid type '{}': {}", param.param_type, e))
+            })?;
+            let (default, is_literal) = default_expr(
+                default,
+                &param.param_type,
+                &format!("parameter '{}'", param.name),
+            )?;
+
+            match component.props.iter().find(|p| p.name == param.name) {
+                Some(prop) if prop.optional => bindings.push(if is_literal {
+                    quote! {
+                        let #name: #param_type = props.#name.clone().unwrap_or(#default);
+                    }
+                } else {
+                    quote! {
+                        let #name: #param_type = props.#name.clone().unwrap_or_else(|| #default);
+                    }
+                }),
+                Some(_) => {
+                    return Err(CompileError::codegen(format!(
+                        "Template parameter '{}' has a default but prop '{}' of component '{}' \
+                         is required; declare it as `{}: {}?`",
+                        param.name, param.name, component.name, param.name, param.param_type
+                    )))
+                }
+                None => bindings.push(quote! {
+                    let #name: #param_type = #default;
+                }),
+            }
+        }
+
+        Ok(quote! {
+            #(#bindings)*
+        })
+    }
+
     /// Check if a type is primitive and should be copied rather than referenced
     fn is_primitive_type(&self, type_name: &str) -> bool {
         matches!(
@@ -956,7 +1370,8 @@ impl CodeGenerator {
                 let props = #props_ident {
                     #(#prop_assignments),*
                 };
-                component.render(&props, context)?
+                let html = context.render_component(#name, component.render(&props, context))?;
+                context.component_boundary(#name, html)
             }
         })
     }
@@ -980,6 +1395,7 @@ impl CodeGenerator {
     ///   * every prop name matches a field on the callee's Props struct
     ///     (only verifiable for same-file callees — out-of-file types are
     ///     opaque here and left to `rustc`)
+    ///
     /// Suggestions are appended to the error message via `suggest::help_line`
     /// so both CLI consumers and the LSP pick them up without structural
     /// changes to `CompileError`.
@@ -1081,7 +1497,13 @@ impl CodeGenerator {
                 }
                 Ok(())
             }
-            TemplateAst::Element { children, .. } => {
+            TemplateAst::Element {
+                tag,
+                attributes,
+                children,
+                ..
+            } => {
+                self.check_inline_handlers(tag, attributes, current_template);
                 for c in children {
                     self.walk_validate(c, known_components, imported_items, current_template)?;
                 }
@@ -1106,7 +1528,8 @@ impl CodeGenerator {
             TemplateAst::For { body, .. } => {
                 self.walk_validate(body, known_components, imported_items, current_template)
             }
-            TemplateAst::Match { arms, .. } => {
+            TemplateAst::Match { expression, arms } => {
+                self.check_match_default_arm(expression, arms, current_template)?;
                 for arm in arms {
                     self.walk_validate(
                         &arm.body,
@@ -1126,11 +1549,54 @@ impl CodeGenerator {
             TemplateAst::Text(_)
             | TemplateAst::Expression(_)
             | TemplateAst::RawExpression(_)
+            | TemplateAst::Debug(_)
             | TemplateAst::Raw(_)
+            | TemplateAst::Comment(_)
             | TemplateAst::Children => Ok(()),
         }
     }
 
+    fn check_inline_handlers(&self, tag: &str, attributes: &[Attribute], current_template: &str) {
+        if !self.lint_inline_handlers {
+            return;
+        }
+        for attr in attributes.iter().filter(|a| is_event_handler_attr(&a.name)) {
+            self.warnings.borrow_mut().push(format!(
+                "`{}` on `<{}>` in template `{}` is an inline event handler; its \
+                 value runs as script in the browser.\n  help: move the script into \
+                 a static file, or set `lint_inline_handlers = false`",
+                attr.name, tag, current_template
+            ));
+        }
+    }
+
+    fn check_match_default_arm(
+        &self,
+        expression: &str,
+        arms: &[MatchArm],
+        current_template: &str,
+    ) -> Result<()> {
+        if self.match_default_arm == MatchDefaultArm::Allow
+            || match_has_default_arm(arms)
+            || matches_enum_variants(arms)
+        {
+            return Ok(());
+        }
+        let message = format!(
+            "`match {}` in template `{}` has no default arm; a missed case fails to \
+             compile in the generated code.\n  help: add `_ => {{ }}` as the last arm",
+            expression.trim(),
+            current_template
+        );
+        match self.match_default_arm {
+            MatchDefaultArm::Deny => Err(CompileError::codegen(message)),
+            _ => {
+                self.warnings.borrow_mut().push(message);
+                Ok(())
+            }
+        }
+    }
+
     /// Recursively checks whether `ast` contains a `TemplateAst::Children`
     /// node anywhere in its subtree. Used to decide whether a component's
     /// Props struct needs the auto-injected `children: Html` field.
@@ -1163,9 +1629,254 @@ impl CodeGenerator {
             TemplateAst::Text(_)
             | TemplateAst::Expression(_)
             | TemplateAst::RawExpression(_)
-            | TemplateAst::Raw(_) => false,
+            | TemplateAst::Debug(_)
+            | TemplateAst::Raw(_)
+            | TemplateAst::Comment(_) => false,
+        }
+    }
+}
+
+/// True when some unguarded arm matches anything (`_`, `other`, `x @ _`), or
+/// the arms spell out both halves of a `bool`, `Option` or `Result`.
+fn match_has_default_arm(arms: &[MatchArm]) -> bool {
+    let unguarded: Vec<&str> = arms
+        .iter()
+        .map(|arm| arm.pattern.trim())
+        .filter(|pattern| !pattern.contains(" if "))
+        .collect();
+    let alternatives = || unguarded.iter().flat_map(|p| p.split('|').map(str::trim));
+
+    let catch_all = alternatives().any(|alt| {
+        let binding = alt.split_once('@').map_or(alt, |(_, rhs)| rhs.trim());
+        binding == "_"
+            || (binding.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                && binding.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !matches!(binding, "true" | "false"))
+    });
+    let covers = |a: &dyn Fn(&str) -> bool, b: &dyn Fn(&str) -> bool| {
+        alternatives().any(a) && alternatives().any(b)
+    };
+
+    catch_all
+        || covers(&|p| p == "true", &|p| p == "false")
+        || covers(&|p| p.starts_with("Some("), &|p| p == "None")
+        || covers(&|p| p.starts_with("Ok("), &|p| p.starts_with("Err("))
+}
+
+/// Whether any arm matches an enum variant path (`Role::Admin`,
+/// `Msg::Text(t)`, `Shape::Rect { .. }`). Such a match can be exhaustive
+/// without a catch-all, which only rustc can tell.
+fn matches_enum_variants(arms: &[MatchArm]) -> bool {
+    arms.iter()
+        .flat_map(|arm| arm.pattern.split('|'))
+        .map(|alt| {
+            let alt = alt.trim();
+            let alt = alt.split_once('@').map_or(alt, |(_, rhs)| rhs.trim());
+            alt.split(['(', '{', ' ']).next().unwrap_or_default()
+        })
+        .any(|path| {
+            path.contains("::")
+                && path
+                    .split("::")
+                    .all(|seg| seg.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+        })
+}
+
+/// Render a subtree that contains no Rust expressions, control flow or
+/// component calls to the exact string the runtime would produce for it
+/// (`HtmlElement::render_to` + `escape_text`/`escape_attribute`). Returns
+/// `None` as soon as any node needs runtime evaluation, and for raw HTML
+/// nodes, which keep going through `Html::raw`'s checks.
+fn static_html(ast: &TemplateAst) -> Option<String> {
+    let mut out = String::new();
+    write_static_html(ast, &mut out).then_some(out)
+}
+
+/// Whether an attribute is an inline event handler. HTML has no other
+/// attributes starting with `on`.
+fn is_event_handler_attr(name: &str) -> bool {
+    name.len() > 2 && name.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("on"))
+}
+
+/// Collect the outermost element subtrees of `ast` that [`static_html`] can
+/// render, without descending into them.
+fn collect_static_subtrees<'a>(ast: &'a TemplateAst, out: &mut Vec<&'a TemplateAst>) {
+    match ast {
+        TemplateAst::Element { children, .. } => {
+            if static_html(ast).is_some() {
+                out.push(ast);
+            } else {
+                for child in children {
+                    collect_static_subtrees(child, out);
+                }
+            }
+        }
+        TemplateAst::If {
+            then_branch,
+            else_branch,
+            ..
+        } => {
+            collect_static_subtrees(then_branch, out);
+            if let Some(else_branch) = else_branch {
+                collect_static_subtrees(else_branch, out);
+            }
+        }
+        TemplateAst::For { body, .. } => collect_static_subtrees(body, out),
+        TemplateAst::Match { arms, .. } => {
+            for arm in arms {
+                collect_static_subtrees(&arm.body, out);
+            }
+        }
+        TemplateAst::Component {
+            children: Some(body),
+            ..
+        } => collect_static_subtrees(body, out),
+        TemplateAst::Fragment(nodes) => {
+            for node in nodes {
+                collect_static_subtrees(node, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn write_static_html(ast: &TemplateAst, out: &mut String) -> bool {
+    match ast {
+        TemplateAst::Text(text) => {
+            // Mirrors the `Html::Empty` emitted for whitespace-only text.
+            if !text.trim().is_empty() {
+                out.push_str(&escape_html(text));
+            }
+            true
+        }
+        TemplateAst::Comment(text) => {
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->");
+            true
+        }
+        TemplateAst::Element {
+            tag,
+            attributes,
+            children,
+            self_closing,
+        } => {
+            out.push('<');
+            out.push_str(tag);
+            for attr in attributes {
+                let AttributeValue::Static(value) = &attr.value else {
+                    return false;
+                };
+                out.push(' ');
+                out.push_str(&attr.name);
+                out.push_str("=\"");
+                out.push_str(&escape_html(value));
+                out.push('"');
+            }
+            if *self_closing || is_void_element(tag) {
+                out.push_str(" />");
+                return true;
+            }
+            out.push('>');
+            for child in children {
+                if !write_static_html(child, out) {
+                    return false;
+                }
+            }
+            out.push_str("</");
+            out.push_str(tag);
+            out.push('>');
+            true
+        }
+        TemplateAst::Fragment(nodes) => nodes.iter().all(|n| write_static_html(n, out)),
+        _ => false,
+    }
+}
+
+/// A prop's `= default` as an expression of the prop's type. String
+/// literals become owned (`"primary"` → `"primary".to_string()`) unless the
+/// prop is itself a reference; anything else is used verbatim. The flag is
+/// true when the expression is a plain literal, cheap enough to evaluate
+/// eagerly.
+fn prop_default_expr(prop: &PropDef) -> Result<Option<(TokenStream, bool)>> {
+    let Some(default) = &prop.default_value else {
+        return Ok(None);
+    };
+    default_expr(default, &prop.prop_type, &format!("prop '{}'", prop.name)).map(Some)
+}
+
+/// [`prop_default_expr`] for any `= default` of type `ty`; `owner` names
+/// what it belongs to in errors. Template parameter defaults go through
+/// here too, so `tone: String = "info"` binds a `String`.
+fn default_expr(default: &str, ty: &str, owner: &str) -> Result<(TokenStream, bool)> {
+    let expr: Expr = parse_str(default.trim()).map_err(|e| {
+        CompileError::codegen(format!(
+            "Invalid default '{}' for {}: {}",
+            default.trim(),
+            owner,
+            e
+        ))
+    })?;
+    match &expr {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(_),
+            ..
+        }) if !ty.trim_start().starts_with('&') => Ok((quote! { #expr.to_string() }, false)),
+        Expr::Lit(_) => Ok((quote! { #expr }, true)),
+        _ => Ok((quote! { #expr }, false)),
+    }
+}
+
+/// Rustdoc lines for a prop field: its `///` comments, then any
+/// `@example(...)` annotations.
+fn prop_doc_lines(prop: &PropDef) -> Vec<String> {
+    let mut docs: Vec<String> = prop.docs.iter().map(|d| format!(" {}", d)).collect();
+    for annotation in &prop.annotations {
+        if let ("example", Some(args)) = (annotation.name.as_str(), &annotation.args) {
+            if !docs.is_empty() {
+                docs.push(String::new());
+            }
+            docs.push(format!(" Example: `{}`", args));
         }
     }
+    docs
+}
+
+/// The `validate()` check for one prop annotation, run against a `value`
+/// binding to the prop. `None` for annotations that only document.
+fn annotation_check(prop: &PropDef, annotation: &PropAnnotation) -> Result<Option<TokenStream>> {
+    let (op, what) = match annotation.name.as_str() {
+        "example" => return Ok(None),
+        "min" => (quote! { *value < }, "be at least"),
+        "max" => (quote! { *value > }, "be at most"),
+        "min_len" => (quote! { value.len() < }, "have a length of at least"),
+        "max_len" => (quote! { value.len() > }, "have a length of at most"),
+        other => {
+            return Err(CompileError::codegen(format!(
+                "Unknown annotation `@{}` on prop '{}'; expected one of \
+                 @min, @max, @min_len, @max_len, @example",
+                other, prop.name
+            )))
+        }
+    };
+    let args = annotation.args.as_deref().ok_or_else(|| {
+        CompileError::codegen(format!(
+            "`@{}` on prop '{}' needs a value, e.g. `@{}(1)`",
+            annotation.name, prop.name, annotation.name
+        ))
+    })?;
+    let limit: Expr = parse_str(args).map_err(|e| {
+        CompileError::codegen(format!(
+            "Invalid `@{}` value '{}' on prop '{}': {}",
+            annotation.name, args, prop.name, e
+        ))
+    })?;
+    let message = format!("prop `{}` must {} {}", prop.name, what, args);
+    Ok(Some(quote! {
+        if #op #limit {
+            return Err(RuitlError::validation(#message));
+        }
+    }))
 }
 
 #[cfg(test)]
@@ -1191,12 +1902,18 @@ mod tests {
                     prop_type: "String".to_string(),
                     optional: false,
                     default_value: None,
+                    docs: Vec::new(),
+                    comments: Vec::new(),
+                    annotations: Vec::new(),
                 },
                 PropDef {
                     name: "disabled".to_string(),
                     prop_type: "bool".to_string(),
                     optional: true,
                     default_value: Some("false".to_string()),
+                    docs: Vec::new(),
+                    comments: Vec::new(),
+                    annotations: Vec::new(),
                 },
             ],
             generics: vec![],
@@ -1249,6 +1966,45 @@ mod tests {
         assert!(normalized.contains("impl ComponentProps"));
     }
 
+    #[test]
+    fn test_generate_props_defaults_and_builder() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let defaulted = crate::parse_str(
+            "component Chip { props { variant: String = \"primary\", disabled: bool = false, note: String? } }",
+        )
+        .unwrap();
+        let out = normalize_ws(
+            &generator
+                .generate_props_struct(&defaulted.components[0])
+                .unwrap()
+                .to_string(),
+        );
+        assert!(out.contains("impl Default for ChipProps"));
+        assert!(out.contains("variant : \"primary\" . to_string ()"));
+        assert!(out.contains("disabled : false"));
+        assert!(out.contains("note : None"));
+        assert!(out.contains("self . disabled . unwrap_or (false)"));
+        assert!(out.contains("pub struct ChipPropsBuilder"));
+
+        let required = crate::parse_str(
+            "component Tag { props { label: String, tone: String = \"info\" } }",
+        )
+        .unwrap();
+        let out = normalize_ws(
+            &generator
+                .generate_props_struct(&required.components[0])
+                .unwrap()
+                .to_string(),
+        );
+        assert!(!out.contains("impl Default for TagProps"));
+        assert!(out.contains("missing required prop `label`"));
+    }
+
     #[test]
     fn test_generate_element_code() {
         let generator = CodeGenerator::new(RuitlFile {
@@ -1275,6 +2031,179 @@ mod tests {
         assert!(normalized.contains("child"));
     }
 
+    #[test]
+    fn test_conditional_boolean_attribute_renders_bare() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let attributes = vec![
+            Attribute {
+                name: "disabled".to_string(),
+                value: AttributeValue::Conditional("busy".to_string()),
+            },
+            Attribute {
+                name: "title".to_string(),
+                value: AttributeValue::Conditional("hint".to_string()),
+            },
+        ];
+        let code = normalize_ws(
+            &generator
+                .generate_element_code("button", &attributes, &[], false)
+                .unwrap()
+                .to_string(),
+        );
+        assert!(code.contains(r#"bool_attr_if ("disabled" , busy)"#), "{}", code);
+        assert!(code.contains(r#"attr_optional ("title" , & hint)"#), "{}", code);
+    }
+
+    #[test]
+    fn test_generate_class_directive_after_class_attr() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        // Directive listed first still merges into the static class.
+        let attributes = vec![
+            Attribute {
+                name: "active".to_string(),
+                value: AttributeValue::ClassToggle("is_active".to_string()),
+            },
+            Attribute {
+                name: "class".to_string(),
+                value: AttributeValue::Static("tab".to_string()),
+            },
+        ];
+
+        let result = generator
+            .generate_element_code("a", &attributes, &[], false)
+            .unwrap();
+
+        let normalized = normalize_ws(&result.to_string());
+        assert!(
+            normalized.contains(r#"attr ("class" , "tab") . class_if ("active" , is_active)"#),
+            "got: {}",
+            normalized
+        );
+    }
+
+    #[test]
+    fn test_generate_debug_directive_gated_on_debug_assertions() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let result = generator
+            .generate_ast_code(&TemplateAst::Debug("user".to_string()))
+            .unwrap();
+
+        let normalized = normalize_ws(&result.to_string());
+        assert!(
+            normalized.starts_with("if cfg ! (debug_assertions)"),
+            "{}",
+            normalized
+        );
+        assert!(
+            normalized.ends_with("else { Html :: Empty }"),
+            "{}",
+            normalized
+        );
+        assert!(
+            normalized.contains("escape_comment (& format ! (\"{:?}\" , user))"),
+            "{}",
+            normalized
+        );
+    }
+
+    #[test]
+    fn test_static_subtree_folds_to_raw_literal() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        })
+        .with_fold_static(true);
+
+        let ast = TemplateAst::Element {
+            tag: "div".to_string(),
+            attributes: vec![Attribute {
+                name: "title".to_string(),
+                value: AttributeValue::Static("a \"b\"".to_string()),
+            }],
+            children: vec![
+                TemplateAst::Text("\n    ".to_string()),
+                TemplateAst::Element {
+                    tag: "p".to_string(),
+                    attributes: vec![],
+                    children: vec![TemplateAst::Text("Hi & bye".to_string())],
+                    self_closing: false,
+                },
+                TemplateAst::Element {
+                    tag: "br".to_string(),
+                    attributes: vec![],
+                    children: vec![],
+                    self_closing: true,
+                },
+            ],
+            self_closing: false,
+        };
+
+        let code = normalize_ws(&generator.generate_ast_code(&ast).unwrap().to_string());
+        assert_eq!(
+            code,
+            r#"Html :: Raw ("<div title=\"a &quot;b&quot;\"><p>Hi &amp; bye</p><br /></div>" . to_string ())"#
+        );
+
+        // Without the option the element tree is built at runtime.
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+        let code = normalize_ws(&generator.generate_ast_code(&ast).unwrap().to_string());
+        assert!(code.starts_with("Html :: Element (HtmlElement :: new (\"div\")"), "{}", code);
+    }
+
+    #[test]
+    fn test_dynamic_subtree_is_not_folded() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        })
+        .with_fold_static(true);
+
+        let ast = TemplateAst::Element {
+            tag: "div".to_string(),
+            attributes: vec![],
+            children: vec![
+                TemplateAst::Element {
+                    tag: "h1".to_string(),
+                    attributes: vec![],
+                    children: vec![TemplateAst::Text("Title".to_string())],
+                    self_closing: false,
+                },
+                TemplateAst::Expression("name".to_string()),
+            ],
+            self_closing: false,
+        };
+
+        let code = normalize_ws(&generator.generate_ast_code(&ast).unwrap().to_string());
+        assert!(code.starts_with("Html :: Element (HtmlElement :: new (\"div\")"), "{}", code);
+        // The static child still folds on its own.
+        assert!(
+            code.contains(r#"Html :: Raw ("<h1>Title</h1>" . to_string ())"#),
+            "{}",
+            code
+        );
+    }
+
     #[test]
     fn test_generate_expression_code() {
         let generator = CodeGenerator::new(RuitlFile {
@@ -1312,6 +2241,65 @@ mod tests {
         assert!(code.contains("else"));
     }
 
+    #[test]
+    fn test_generate_else_if_chain() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let else_branch = Some(Box::new(TemplateAst::If {
+            condition: "b".to_string(),
+            then_branch: Box::new(TemplateAst::Text("B".to_string())),
+            else_branch: Some(Box::new(TemplateAst::Text("C".to_string()))),
+        }));
+        let result = generator
+            .generate_if_code("a", &TemplateAst::Text("A".to_string()), &else_branch)
+            .unwrap();
+
+        let code = normalize_ws(&result.to_string());
+        assert!(code.contains("} else if b {"), "{}", code);
+        assert!(!code.contains("else { if"), "{}", code);
+    }
+
+    #[test]
+    fn test_html_comment_is_emitted_verbatim() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let comment = TemplateAst::Comment(" TODO: fix layout ".to_string());
+        let code = generator.generate_ast_code(&comment).unwrap().to_string();
+        assert!(
+            code.contains(r#"Html :: Raw ("<!-- TODO: fix layout -->" . to_string ())"#),
+            "{}",
+            code
+        );
+
+        // Comments don't stop a static subtree from folding.
+        let element = TemplateAst::Element {
+            tag: "div".to_string(),
+            attributes: vec![],
+            children: vec![comment, TemplateAst::Text("hi".to_string())],
+            self_closing: false,
+        };
+        let code = generator
+            .with_fold_static(true)
+            .generate_ast_code(&element)
+            .unwrap()
+            .to_string();
+        assert!(
+            code.contains(
+                r#"Html :: Raw ("<div><!-- TODO: fix layout -->hi</div>" . to_string ())"#
+            ),
+            "{}",
+            code
+        );
+    }
+
     #[test]
     fn test_generate_for_code() {
         let generator = CodeGenerator::new(RuitlFile {
@@ -1335,6 +2323,70 @@ mod tests {
         assert!(code.contains("item"));
     }
 
+    #[test]
+    fn test_for_over_numeric_ranges() {
+        let src = r#"
+component Steps {
+    props {
+        items: Vec<String>,
+        n: usize,
+    }
+}
+
+ruitl Steps(items: Vec<String>, n: usize) {
+    <ol>
+        for i in 0..n { <li>{i}</li> }
+        for i in (0..items.len()) { <li>{i}: {items[i]}</li> }
+        for i in (1..=n).rev() { <li>{i}</li> }
+    </ol>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let code = normalize_ws(&generator.generate().unwrap().to_string());
+
+        // A bare range is parenthesized so `.into_iter()` applies to the
+        // whole range, not just its upper bound.
+        assert!(code.contains("(0 .. n) . into_iter ()"), "{}", code);
+        assert!(!code.contains("0 .. n . into_iter"), "{}", code);
+        assert!(
+            code.contains("(0 .. items . len ()) . into_iter ()"),
+            "{}",
+            code
+        );
+        assert!(code.contains("(1 ..= n) . rev () . into_iter ()"), "{}", code);
+        assert!(code.contains("items [i]"), "{}", code);
+    }
+
+    #[test]
+    fn test_question_mark_inside_loop_body_propagates() {
+        let generator = CodeGenerator::new(RuitlFile {
+            components: vec![],
+            templates: vec![],
+            imports: vec![],
+        });
+
+        let body = TemplateAst::Element {
+            tag: "li".to_string(),
+            attributes: vec![],
+            children: vec![TemplateAst::Expression("raw.parse::<u32>()?".to_string())],
+            self_closing: false,
+        };
+
+        let code = normalize_ws(
+            &generator
+                .generate_for_code("raw", "scores", &body)
+                .unwrap()
+                .to_string(),
+        );
+        assert!(code.contains(". map (| raw | Ok ("), "{}", code);
+        assert!(
+            code.contains(". collect :: < Result < Vec < _ >> > () ?"),
+            "{}",
+            code
+        );
+        assert!(code.contains("raw . parse :: < u32 > () ?"), "{}", code);
+    }
+
     #[test]
     fn test_generate_component_invocation() {
         let generator = CodeGenerator::new(RuitlFile {
@@ -1364,6 +2416,10 @@ mod tests {
         assert!(normalized.contains("ButtonProps"));
         assert!(normalized.contains("text : \"Click me\""));
         assert!(normalized.contains("disabled : false"));
+        assert!(normalized.contains("context . component_boundary (\"Button\""));
+        assert!(normalized.contains(
+            "context . render_component (\"Button\" , component . render (& props , context))"
+        ));
     }
 
     #[test]
@@ -1383,6 +2439,278 @@ mod tests {
         assert!(code.contains("fn render"));
     }
 
+    #[test]
+    fn test_template_param_default_binds_optional_prop() {
+        let src = r#"
+component Badge {
+    props {
+        label: String,
+        tone: String?,
+    }
+}
+
+ruitl Badge(label: String, tone: String = "info".to_string()) {
+    <span class={tone}>{label}</span>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let code = normalize_ws(&generator.generate().unwrap().to_string());
+
+        assert!(
+            code.contains(
+                r#"let tone : String = props . tone . clone () . unwrap_or_else (|| "info" . to_string ()) ;"#
+            ),
+            "{}",
+            code
+        );
+        assert!(!code.contains("let tone = & props . tone ;"), "{}", code);
+    }
+
+    #[test]
+    fn test_repeated_static_fragment_is_shared() {
+        let src = r#"
+component Panel {
+    props {
+        open: bool,
+        label: String,
+    }
+}
+
+ruitl Panel(open: bool, label: String) {
+    if open {
+        <strong><span class="icon">*</span>{label}</strong>
+    } else {
+        <em><span class="icon">*</span>{label}</em>
+    }
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let code = normalize_ws(&generator.generate().unwrap().to_string());
+
+        assert_eq!(
+            code.matches(r#"HtmlElement :: new ("span")"#).count(),
+            1,
+            "{}",
+            code
+        );
+        assert!(
+            code.contains("let __ruitl_static_0 : Html = Html :: Element"),
+            "{}",
+            code
+        );
+        assert_eq!(
+            code.matches("__ruitl_static_0 . clone ()").count(),
+            2,
+            "{}",
+            code
+        );
+        // The dynamic parents are still built at their own sites.
+        assert!(code.contains(r#"HtmlElement :: new ("strong")"#), "{}", code);
+        assert!(code.contains(r#"HtmlElement :: new ("em")"#), "{}", code);
+    }
+
+    #[test]
+    fn test_unused_props_and_context_are_not_bound() {
+        let src = r#"
+component Notice {
+    props {
+        title: String,
+        footnote: String,
+        count: u32,
+    }
+}
+
+ruitl Notice(title: String, footnote: String, count: u32) {
+    <p>{title}</p>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let code = normalize_ws(&generator.generate().unwrap().to_string());
+
+        assert!(code.contains("let title = & props . title ;"), "{}", code);
+        assert!(!code.contains("props . footnote"), "{}", code);
+        assert!(!code.contains("props . count"), "{}", code);
+        assert!(code.contains("_context : & ComponentContext"), "{}", code);
+        assert!(code.contains("# [allow (unused_variables)] fn render"), "{}", code);
+    }
+
+    #[test]
+    fn test_template_param_default_on_required_prop_is_rejected() {
+        let src = r#"
+component Badge {
+    props {
+        tone: String,
+    }
+}
+
+ruitl Badge(tone: String = "info".to_string()) {
+    <span>{tone}</span>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let err = generator.generate().unwrap_err().to_string();
+        assert!(err.contains("declare it as `tone: String?`"), "{}", err);
+    }
+
+    #[test]
+    fn test_prop_annotations_emit_docs_and_validation() {
+        let src = r#"
+component Cart {
+    props {
+        /// Number of items
+        @min(1) @example(3)
+        count: u32,
+        @max_len(20)
+        coupon: String?,
+    }
+}
+
+ruitl Cart(count: u32) {
+    <p>{count}</p>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let code = normalize_ws(&generator.generate().unwrap().to_string());
+
+        assert!(
+            code.contains(r#"# [doc = " Number of items"] # [doc = ""] # [doc = " Example: `3`"] pub count : u32"#),
+            "{}",
+            code
+        );
+        assert!(
+            code.contains(
+                r#"let value = & self . count ; if * value < 1 { return Err (RuitlError :: validation ("prop `count` must be at least 1")) ; }"#
+            ),
+            "{}",
+            code
+        );
+        assert!(
+            code.contains(
+                r#"if let Some (value) = & self . coupon { if value . len () > 20 { return Err (RuitlError :: validation ("prop `coupon` must have a length of at most 20")) ; } }"#
+            ),
+            "{}",
+            code
+        );
+    }
+
+    #[test]
+    fn test_unknown_prop_annotation_is_rejected() {
+        let src = "component C { props { @positive n: i32, } }\nruitl C(n: i32) { <p>{n}</p> }";
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        let err = generator.generate().unwrap_err().to_string();
+        assert!(
+            err.contains("Unknown annotation `@positive` on prop 'n'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_match_without_default_arm_is_diagnosed() {
+        let src = r#"
+component Status {
+    props {
+        state: String,
+    }
+}
+
+ruitl Status(state: String) {
+    match state.as_str() {
+        "on" => { <b>on</b> }
+        "off" => { <i>off</i> }
+    }
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        generator.generate().unwrap();
+        let warnings = generator.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("`match state.as_str()` in template `Status`"));
+        assert!(warnings[0].contains("add `_ => { }`"));
+
+        let mut strict = CodeGenerator::new(crate::parse_str(src).unwrap())
+            .with_match_default_arm(MatchDefaultArm::Deny);
+        assert!(strict.generate().is_err());
+
+        let mut lenient = CodeGenerator::new(crate::parse_str(src).unwrap())
+            .with_match_default_arm(MatchDefaultArm::Allow);
+        lenient.generate().unwrap();
+        assert!(lenient.warnings().is_empty());
+
+        let enum_src = r#"
+component Badge {
+    props {
+        role: Role,
+    }
+}
+
+ruitl Badge(role: Role) {
+    match role {
+        Role::Admin => { <b>admin</b> }
+        Role::User => { <i>user</i> }
+    }
+}
+"#;
+        let mut strict = CodeGenerator::new(crate::parse_str(enum_src).unwrap())
+            .with_match_default_arm(MatchDefaultArm::Deny);
+        strict.generate().unwrap();
+        assert!(strict.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_inline_handler_lint_is_opt_in() {
+        let src = r#"
+component Counter {
+    props {
+        count: u32,
+    }
+}
+
+ruitl Counter(count: u32) {
+    <div class="counter">
+        <button onclick="increment()">{count}</button>
+    </div>
+}
+"#;
+        let mut generator = CodeGenerator::new(crate::parse_str(src).unwrap());
+        generator.generate().unwrap();
+        assert!(generator.warnings().is_empty());
+
+        let mut linted =
+            CodeGenerator::new(crate::parse_str(src).unwrap()).with_lint_inline_handlers(true);
+        linted.generate().unwrap();
+        let warnings = linted.warnings();
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].contains("`onclick` on `<button>` in template `Counter`"));
+    }
+
+    #[test]
+    fn test_match_default_arm_detection() {
+        let arms = |patterns: &[&str]| -> Vec<MatchArm> {
+            patterns
+                .iter()
+                .map(|p| MatchArm {
+                    pattern: p.to_string(),
+                    body: TemplateAst::Fragment(vec![]),
+                })
+                .collect()
+        };
+        assert!(match_has_default_arm(&arms(&["\"a\"", "_"])));
+        assert!(match_has_default_arm(&arms(&["1", "other"])));
+        assert!(match_has_default_arm(&arms(&["true", "false"])));
+        assert!(match_has_default_arm(&arms(&["Some(x)", "None"])));
+        assert!(!match_has_default_arm(&arms(&["\"a\"", "_ if flag"])));
+        assert!(!match_has_default_arm(&arms(&["Role::Admin", "Role::User"])));
+        assert!(!match_has_default_arm(&arms(&["None"])));
+
+        assert!(matches_enum_variants(&arms(&["Role::Admin", "Role::User"])));
+        assert!(matches_enum_variants(&arms(&[
+            "Msg::Text(t) if t.is_empty()",
+            "crate::Msg::Ping { .. } | Msg::Quit"
+        ])));
+        assert!(!matches_enum_variants(&arms(&["\"a\"", "1", "Some(x)"])));
+    }
+
     #[test]
     fn test_generics_emit_on_props_and_component_structs() {
         use crate::parser::GenericParam;
@@ -1393,6 +2721,9 @@ mod tests {
                 prop_type: "T".to_string(),
                 optional: false,
                 default_value: None,
+                docs: Vec::new(),
+                comments: Vec::new(),
+                annotations: Vec::new(),
             }],
             generics: vec![GenericParam {
                 name: "T".to_string(),
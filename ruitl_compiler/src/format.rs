@@ -16,15 +16,25 @@
 //! `format_source` twice on any input produces the same output on the
 //! second pass. Tests enforce this.
 
-use crate::error::Result;
+use crate::error::{CompileError, Result};
 use crate::parser::{
     Attribute, AttributeValue, ComponentDef, GenericParam, ImportDef, MatchArm, ParamDef,
     PropDef, PropValue, RuitlFile, RuitlParser, TemplateAst, TemplateDef,
 };
 
-/// Parse `source` and reprint it in canonical form.
+/// Parse `source` and reprint it in canonical form. Fails on comments
+/// inside opening tags, which the AST doesn't keep and formatting would
+/// delete.
 pub fn format_source(source: &str) -> Result<String> {
-    let file = RuitlParser::new(source.to_string()).parse()?;
+    let mut parser = RuitlParser::new(source.to_string());
+    let file = parser.parse()?;
+    if let Some(comment) = parser.tag_comments().first() {
+        return Err(CompileError::parse(format!(
+            "cannot format: the comment `{}` inside a tag would be lost; \
+             move it out of the tag",
+            comment
+        )));
+    }
     Ok(format_file(&file))
 }
 
@@ -122,6 +132,27 @@ fn write_component(out: &mut String, comp: &ComponentDef) {
 }
 
 fn write_prop_def(out: &mut String, prop: &PropDef, indent: usize) {
+    write_leading_comments(out, &prop.comments, indent);
+    for doc in &prop.docs {
+        pad(out, indent);
+        out.push_str("///");
+        if !doc.is_empty() {
+            out.push(' ');
+            out.push_str(doc);
+        }
+        out.push('\n');
+    }
+    for annotation in &prop.annotations {
+        pad(out, indent);
+        out.push('@');
+        out.push_str(&annotation.name);
+        if let Some(args) = &annotation.args {
+            out.push('(');
+            out.push_str(args);
+            out.push(')');
+        }
+        out.push('\n');
+    }
     pad(out, indent);
     out.push_str(&prop.name);
     out.push_str(": ");
@@ -158,6 +189,10 @@ fn write_param(out: &mut String, param: &ParamDef) {
     out.push_str(&param.name);
     out.push_str(": ");
     out.push_str(&param.param_type);
+    if let Some(default) = &param.default_value {
+        out.push_str(" = ");
+        out.push_str(default.trim());
+    }
 }
 
 fn write_generics(out: &mut String, generics: &[GenericParam]) {
@@ -217,11 +252,23 @@ fn write_node(out: &mut String, ast: &TemplateAst, indent: usize) {
             out.push_str(expr.trim());
             out.push_str("}\n");
         }
+        TemplateAst::Debug(expr) => {
+            pad(out, indent);
+            out.push_str("@debug(");
+            out.push_str(expr.trim());
+            out.push_str(")\n");
+        }
         TemplateAst::Raw(html) => {
             pad(out, indent);
             out.push_str(html);
             out.push('\n');
         }
+        TemplateAst::Comment(text) => {
+            pad(out, indent);
+            out.push_str("<!--");
+            out.push_str(text);
+            out.push_str("-->\n");
+        }
         TemplateAst::Element {
             tag,
             attributes,
@@ -248,14 +295,13 @@ fn write_node(out: &mut String, ast: &TemplateAst, indent: usize) {
                 // without an extra nested block.
                 if matches!(&**else_b, TemplateAst::If { .. }) {
                     let mut inner = String::new();
-                    write_node(&mut inner, else_b, 0);
-                    out.push_str(inner.trim_start());
+                    write_node(&mut inner, else_b, indent);
+                    out.push_str(&inner[indent..]);
                 } else {
                     out.push_str("{\n");
                     write_template_body(out, else_b, indent + 4);
                     pad(out, indent);
                     out.push_str("}\n");
-                    return;
                 }
             } else {
                 out.push('\n');
@@ -450,6 +496,9 @@ fn try_inline_children(children: &[TemplateAst]) -> Option<String> {
 }
 
 fn write_attribute(out: &mut String, attr: &Attribute) {
+    if matches!(attr.value, AttributeValue::ClassToggle(_)) {
+        out.push_str("class:");
+    }
     out.push_str(&attr.name);
     match &attr.value {
         AttributeValue::Static(v) if v == "true" => {
@@ -471,6 +520,11 @@ fn write_attribute(out: &mut String, attr: &Attribute) {
             out.push_str(cond.trim());
             out.push('}');
         }
+        AttributeValue::ClassToggle(cond) => {
+            out.push_str("={");
+            out.push_str(cond.trim());
+            out.push('}');
+        }
     }
 }
 
@@ -516,6 +570,39 @@ mod tests {
         assert_eq!(once, twice, "formatter should be idempotent");
     }
 
+    #[test]
+    fn keeps_html_comments() {
+        let input = "component C { props { x: String, } }\n\
+                     ruitl C(x: String) { <div><!-- TODO: fix layout --><p>{x}</p></div> }";
+        let out = roundtrip(input);
+        assert!(out.contains("        <!-- TODO: fix layout -->\n"), "{}", out);
+        assert_eq!(out, roundtrip(&out));
+    }
+
+    #[test]
+    fn keeps_prop_docs_and_annotations() {
+        let input = "component C { props {\n/// How many\n@min(1) @example(3)\ncount: u32, } }\n\
+                     ruitl C(count: u32) { <p>{count}</p> }";
+        let out = roundtrip(input);
+        assert!(out.contains(
+            "        /// How many\n        @min(1)\n        @example(3)\n        count: u32,"
+        ));
+        assert_eq!(out, roundtrip(&out));
+    }
+
+    #[test]
+    fn keeps_plain_comments_in_props() {
+        let input = "component C { props {\n// TODO: rename\n/// How many\ncount: u32,\n/* legacy */ label: String, } }\n\
+                     ruitl C(count: u32, label: String) { <p>{label}</p> }";
+        let out = roundtrip(input);
+        assert!(
+            out.contains("        // TODO: rename\n        /// How many\n        count: u32,\n        // legacy\n        label: String,"),
+            "{}",
+            out
+        );
+        assert_eq!(out, roundtrip(&out));
+    }
+
     #[test]
     fn formats_optional_and_default_props() {
         let input = "component B { props { t: String, v: String = \"primary\", d: bool?, } }\n\
@@ -525,6 +612,15 @@ mod tests {
         assert!(out.contains("d: bool?,"));
     }
 
+    #[test]
+    fn formats_template_param_defaults() {
+        let input = "component B { props { t: String?, } }\n\
+                     ruitl B(t: String = String::from(\"x\")) { <b>{t}</b> }";
+        let out = roundtrip(input);
+        assert!(out.contains("ruitl B(t: String = String::from(\"x\")) {"), "{}", out);
+        assert_eq!(roundtrip(&out), out);
+    }
+
     #[test]
     fn formats_generics() {
         let input =
@@ -547,6 +643,29 @@ mod tests {
         assert!(out.contains("} else {"));
     }
 
+    #[test]
+    fn refuses_to_drop_comments_inside_tags() {
+        let input = "component C { props { x: String, } }\n\
+                     ruitl C(x: String) { <div /* keep me */ class=\"x\">{x}</div> }";
+        let err = format_source(input).unwrap_err().to_string();
+        assert!(err.contains("`keep me` inside a tag would be lost"), "{}", err);
+    }
+
+    #[test]
+    fn indents_else_if_chains() {
+        let input = "component G { props { n: u32, } }\n\
+                     ruitl G(n: u32) { <div>if n == 0 { <em>none</em> } else if n == 1 { <em>one</em> } else { <em>many</em> }</div> }";
+        let out = roundtrip(input);
+        assert!(
+            out.contains(
+                "        if n == 0 {\n            <em>none</em>\n        } else if n == 1 {\n            <em>one</em>\n        } else {\n            <em>many</em>\n        }\n"
+            ),
+            "{}",
+            out
+        );
+        assert_eq!(roundtrip(&out), out);
+    }
+
     #[test]
     fn preserves_leading_comments_above_declarations() {
         let input = "// top comment\ncomponent Foo { props { x: String } }\n\
@@ -556,6 +675,27 @@ mod tests {
         assert!(out.contains("// ruitl header"));
     }
 
+    #[test]
+    fn formats_class_directive() {
+        let input = "component T { props { on: bool, } }\n\
+                     ruitl T(on: bool) { <a class=\"tab\" class:active={on}>x</a> }";
+        let out = roundtrip(input);
+        assert!(
+            out.contains(r#"<a class="tab" class:active={on}>"#),
+            "{out}"
+        );
+        assert_eq!(out, roundtrip(&out));
+    }
+
+    #[test]
+    fn formats_debug_directive() {
+        let input = "component D { props { items: Vec<String>, } }\n\
+                     ruitl D(items: Vec<String>) { <div>@debug( items.len() )</div> }";
+        let out = roundtrip(input);
+        assert!(out.contains("@debug(items.len())"), "{out}");
+        assert_eq!(out, roundtrip(&out));
+    }
+
     #[test]
     fn formats_nested_elements_with_indentation() {
         let input = "component L { props {} }\n\
@@ -9,6 +9,7 @@
 pub mod codegen;
 pub mod error;
 pub mod format;
+pub mod markup;
 pub mod parser;
 pub mod suggest;
 
@@ -18,18 +19,89 @@ use std::path::{Path, PathBuf};
 /// Bumped whenever codegen output changes shape. Used as a cache-buster in
 /// the sibling-file hash header so `cargo build` invalidates cached output
 /// after any codegen.rs change, even if the `.ruitl` source is unchanged.
-pub const CODEGEN_VERSION: u32 = 2;
+pub const CODEGEN_VERSION: u32 = 19;
 
 /// Marker on the first line of every generated sibling file. The build
 /// pipeline reads the hash off this line before deciding whether to skip
 /// regeneration.
 const HASH_HEADER_PREFIX: &str = "// ruitl-hash: ";
 
-pub use codegen::CodeGenerator;
+/// Second line of every generated sibling file: the components `mod.rs`
+/// registers from it, so the directory index is written without parsing
+/// the sources again.
+const COMPONENTS_HEADER_PREFIX: &str = "// ruitl-components: ";
+
+/// Stable marker on the first line of every generated `mod.rs`. Tooling
+/// (e.g. a `clean` command) keys off it to tell generated files from
+/// hand-written ones, so it is never affected by [`CompileOptions::header`].
+pub const GENERATED_MARKER: &str = "@generated by ruitl_compiler — do not edit.";
+
+/// Knobs for the sibling-file pipeline. The default matches the output of
+/// the plain `compile_*` functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// Text written after [`GENERATED_MARKER`] at the top of the generated
+    /// `mod.rs`. Each line becomes a `//` comment; empty means marker only.
+    pub header: String,
+    /// Rust edition passed to `rustfmt` when formatting generated code.
+    pub edition: String,
+    /// Pre-render fully static element subtrees into one `Html::Raw`
+    /// literal each, instead of building them at runtime. Faster to render,
+    /// but a folded subtree is a single opaque node: `get_attr`/`has_class`,
+    /// `sanitize_urls`, `to_dom_json`, `validate_nesting`, `map_elements`
+    /// and test assertions counting elements don't see inside it. Off by
+    /// default.
+    pub fold_static: bool,
+    /// Warn about inline event handler attributes (`onclick` and friends)
+    /// in templates. Off by default.
+    pub lint_inline_handlers: bool,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            header: "Regenerated on each compile.".to_string(),
+            edition: "2021".to_string(),
+            fold_static: false,
+            lint_inline_handlers: false,
+        }
+    }
+}
+
+impl CompileOptions {
+    /// Arguments passed to `rustfmt` when formatting generated code.
+    pub fn rustfmt_args(&self) -> Vec<String> {
+        vec![
+            "--edition".to_string(),
+            self.edition.clone(),
+            "--emit".to_string(),
+            "stdout".to_string(),
+        ]
+    }
+
+    /// The comment block that opens a generated `mod.rs`, ending in a blank
+    /// line.
+    pub fn mod_file_header(&self) -> String {
+        let mut lines = self.header.lines();
+        let mut out = format!("// {}", GENERATED_MARKER);
+        if let Some(first) = lines.next() {
+            out.push(' ');
+            out.push_str(first);
+        }
+        for line in lines {
+            out.push_str("\n// ");
+            out.push_str(line);
+        }
+        out.push_str("\n\n");
+        out
+    }
+}
+
+pub use codegen::{CodeGenerator, MatchDefaultArm};
 pub use error::{CompileError, Result};
 pub use parser::{
-    Attribute, AttributeValue, ComponentDef, ImportDef, MatchArm, ParamDef, PropDef, PropValue,
-    RuitlFile, RuitlParser, TemplateAst, TemplateDef,
+    Attribute, AttributeValue, ComponentDef, ImportDef, MatchArm, ParamDef, PropAnnotation,
+    PropDef, PropValue, RuitlFile, RuitlParser, TemplateAst, TemplateDef,
 };
 
 /// Parse a `.ruitl` source string into a [`RuitlFile`] AST.
@@ -39,24 +111,65 @@ pub fn parse_str(source: &str) -> Result<RuitlFile> {
 
 /// Generate Rust code (as a formatted string) from a [`RuitlFile`].
 pub fn generate(file: RuitlFile) -> Result<String> {
-    let mut gen = CodeGenerator::new(file);
+    generate_with_warnings(file).map(|(code, _)| code)
+}
+
+/// [`generate`], also returning the generator's non-fatal diagnostics
+/// (see [`CodeGenerator::warnings`]).
+pub fn generate_with_warnings(file: RuitlFile) -> Result<(String, Vec<String>)> {
+    generate_with_options(file, &CompileOptions::default())
+}
+
+/// [`generate_with_warnings`], formatting with the edition from `options`.
+pub fn generate_with_options(
+    file: RuitlFile,
+    options: &CompileOptions,
+) -> Result<(String, Vec<String>)> {
+    let mut gen = CodeGenerator::new(file)
+        .with_fold_static(options.fold_static)
+        .with_lint_inline_handlers(options.lint_inline_handlers);
     let tokens = gen.generate()?;
-    Ok(format_rust(tokens.to_string()))
+    Ok((format_rust(tokens.to_string(), options), gen.warnings()))
 }
 
 /// Compile a single `.ruitl` file to a sibling `*_ruitl.rs` file.
 ///
 /// The output path is `<parent>/<stem>_ruitl.rs` next to the source.
-/// Returns the path that was written.
+/// Returns the path that was written. A source that defines no components
+/// or templates (e.g. an empty file) is an error — see
+/// [`compile_dir_sibling_report`] for the skipping variant.
 pub fn compile_file_sibling(source: &Path) -> Result<PathBuf> {
+    compile_file_sibling_with(source, &CompileOptions::default())
+}
+
+/// [`compile_file_sibling`] with a custom `rustfmt` edition. Does not touch
+/// the directory's `mod.rs`; see [`write_mod_file`].
+pub fn compile_file_sibling_with(source: &Path, options: &CompileOptions) -> Result<PathBuf> {
+    compile_file_sibling_opt(source, options)?
+        .map(|(out, _)| out)
+        .ok_or_else(|| empty_source_error(source))
+}
+
+/// Where [`compile_file_sibling`] writes the output for `source`.
+pub fn sibling_path(source: &Path) -> Result<PathBuf> {
     let stem = source
         .file_stem()
         .and_then(|s| s.to_str())
         .ok_or_else(|| CompileError::parse(format!("invalid file name: {}", source.display())))?;
     let parent = source.parent().unwrap_or_else(|| Path::new("."));
-    let out = parent.join(format!("{}_ruitl.rs", sanitize_stem(stem)));
-    compile_file(source, &out)?;
-    Ok(out)
+    Ok(parent.join(format!("{}_ruitl.rs", sanitize_stem(stem))))
+}
+
+/// A written sibling file and the codegen warnings raised for it.
+type SiblingOutput = (PathBuf, Vec<String>);
+
+/// Like [`compile_file_sibling`], but `Ok(None)` for an empty source.
+fn compile_file_sibling_opt(
+    source: &Path,
+    options: &CompileOptions,
+) -> Result<Option<SiblingOutput>> {
+    let out = sibling_path(source)?;
+    Ok(compile_file_opt(source, &out, options)?.map(|warnings| (out, warnings)))
 }
 
 /// Compile a single `.ruitl` file to the given output path.
@@ -66,22 +179,48 @@ pub fn compile_file_sibling(source: &Path) -> Result<PathBuf> {
 /// left untouched. This avoids touching `mtime` on every build and keeps
 /// `git diff` clean after no-op rebuilds.
 pub fn compile_file(source: &Path, output: &Path) -> Result<()> {
+    match compile_file_opt(source, output, &CompileOptions::default())? {
+        Some(_) => Ok(()),
+        None => Err(empty_source_error(source)),
+    }
+}
+
+/// Shared body of [`compile_file`]. Returns `None` (writing nothing) when
+/// the source defines no components or templates, otherwise the codegen
+/// warnings. Up-to-date outputs are not regenerated, so they report none.
+fn compile_file_opt(
+    source: &Path,
+    output: &Path,
+    options: &CompileOptions,
+) -> Result<Option<Vec<String>>> {
     let src = fs::read_to_string(source)?;
-    let hash = compute_hash(&src);
+    let hash = compute_hash(&src, options);
 
     if output.exists() {
         if let Ok(existing) = fs::read_to_string(output) {
             if let Some(existing_hash) = extract_hash(&existing) {
                 if existing_hash == hash {
-                    return Ok(());
+                    return Ok(Some(Vec::new()));
                 }
             }
         }
     }
 
     let ast = parse_str(&src)?;
-    let code = generate(ast)?;
-    let final_text = format!("{}{}\n{}", HASH_HEADER_PREFIX, hash, code);
+    if ast.components.is_empty() && ast.templates.is_empty() {
+        // Drop output left over from when the source defined something, so
+        // it can't linger in `mod.rs`.
+        if is_generated_sibling(output) {
+            fs::remove_file(output)?;
+        }
+        return Ok(None);
+    }
+    let components = registrable_components(&ast).join(", ");
+    let (code, warnings) = generate_with_options(ast, options)?;
+    let final_text = format!(
+        "{}{}\n{}{}\n{}",
+        HASH_HEADER_PREFIX, hash, COMPONENTS_HEADER_PREFIX, components, code
+    );
 
     if let Some(parent) = output.parent() {
         if !parent.as_os_str().is_empty() {
@@ -89,67 +228,121 @@ pub fn compile_file(source: &Path, output: &Path) -> Result<()> {
         }
     }
     fs::write(output, final_text)?;
-    Ok(())
+    Ok(Some(warnings))
 }
 
-/// MD5 of the source + codegen version, hex-encoded. Not cryptographic —
-/// just a cheap content fingerprint to detect unchanged inputs.
-fn compute_hash(source: &str) -> String {
-    let digest = md5::compute(format!("{}|v{}", source, CODEGEN_VERSION));
+fn empty_source_error(source: &Path) -> CompileError {
+    CompileError::parse(format!(
+        "{}: no component or template definitions",
+        source.display()
+    ))
+}
+
+/// MD5 of the source + codegen version + the options that shape the output
+/// (edition, header, static folding when on), hex-encoded. Not
+/// cryptographic — just a cheap content fingerprint to detect unchanged
+/// inputs.
+fn compute_hash(source: &str, options: &CompileOptions) -> String {
+    let fold = if options.fold_static { "|fold" } else { "" };
+    let digest = md5::compute(format!(
+        "{}|v{}|{}|{}{}",
+        source, CODEGEN_VERSION, options.edition, options.header, fold
+    ));
     format!("{:x}", digest)
 }
 
+/// Whether `path` is a sibling file the compiler wrote.
+fn is_generated_sibling(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|content| content.starts_with(HASH_HEADER_PREFIX))
+        .unwrap_or(false)
+}
+
 /// Pull the digest out of a sibling file's first line, if present.
 fn extract_hash(content: &str) -> Option<&str> {
     let first_line = content.lines().next()?;
     first_line.strip_prefix(HASH_HEADER_PREFIX).map(str::trim)
 }
 
+/// Outcome of [`compile_dir_sibling_report`].
+#[derive(Debug, Default)]
+pub struct DirCompileReport {
+    /// Sibling `*_ruitl.rs` files written (or already up to date).
+    pub outputs: Vec<PathBuf>,
+    /// `.ruitl` sources that define no components or templates. They get no
+    /// sibling file and no `mod.rs` entry; callers should warn about them.
+    pub skipped: Vec<PathBuf>,
+    /// Codegen warnings, each prefixed with its source path. Only files that
+    /// were regenerated on this run contribute.
+    pub warnings: Vec<String>,
+}
+
 /// Walk a directory for `.ruitl` files and compile each into a sibling
 /// `*_ruitl.rs` file. Also writes a top-level `mod.rs` in `dir` that declares
 /// and re-exports each compiled module, so consumers can `mod templates;`.
-/// Returns the list of written output paths.
+/// Returns the list of written output paths. Empty sources are skipped
+/// silently; use [`compile_dir_sibling_report`] to surface them.
 pub fn compile_dir_sibling(dir: &Path) -> Result<Vec<PathBuf>> {
+    compile_dir_sibling_report(dir).map(|report| report.outputs)
+}
+
+/// [`compile_dir_sibling`], also reporting the sources that were skipped
+/// because they define nothing to compile and any codegen warnings.
+pub fn compile_dir_sibling_report(dir: &Path) -> Result<DirCompileReport> {
+    compile_dir_sibling_report_with(dir, &CompileOptions::default())
+}
+
+/// [`compile_dir_sibling_report`] with a custom `mod.rs` header and
+/// `rustfmt` edition.
+pub fn compile_dir_sibling_report_with(
+    dir: &Path,
+    options: &CompileOptions,
+) -> Result<DirCompileReport> {
     if !dir.exists() {
-        return Ok(Vec::new());
+        return Ok(DirCompileReport::default());
     }
     // Collect `.ruitl` paths first so the expensive parse+codegen step can
     // fan out across threads. `walkdir` is single-threaded by construction.
-    let inputs: Vec<PathBuf> = walkdir::WalkDir::new(dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path().is_file()
-                && e.path().extension().map(|x| x == "ruitl").unwrap_or(false)
-        })
-        .map(|e| e.path().to_path_buf())
-        .collect();
+    let inputs = ruitl_sources(dir);
 
     // Fan-out: each file writes to a distinct `<stem>_ruitl.rs` output, so
     // there is no write contention. Errors from one file don't short-circuit
     // the others — collect them all, then report the first so CI logs are
     // deterministic. With `parallel` off (rayon absent) this reduces to a
     // plain `iter()`.
-    let results: Vec<Result<PathBuf>> = {
+    let results: Vec<Result<Option<SiblingOutput>>> = {
         #[cfg(feature = "parallel")]
         {
             use rayon::prelude::*;
             inputs
                 .par_iter()
-                .map(|p| compile_file_sibling(p))
+                .map(|p| compile_file_sibling_opt(p, options))
                 .collect()
         }
         #[cfg(not(feature = "parallel"))]
         {
-            inputs.iter().map(|p| compile_file_sibling(p)).collect()
+            inputs
+                .iter()
+                .map(|p| compile_file_sibling_opt(p, options))
+                .collect()
         }
     };
 
     let mut outputs = Vec::with_capacity(results.len());
+    let mut skipped = Vec::new();
+    let mut warnings = Vec::new();
     let mut first_err: Option<CompileError> = None;
-    for r in results {
+    for (input, r) in inputs.iter().zip(results) {
         match r {
-            Ok(p) => outputs.push(p),
+            Ok(Some((p, file_warnings))) => {
+                outputs.push(p);
+                warnings.extend(
+                    file_warnings
+                        .into_iter()
+                        .map(|w| format!("{}: {}", input.display(), w)),
+                );
+            }
+            Ok(None) => skipped.push(input.clone()),
             Err(e) => {
                 if first_err.is_none() {
                     first_err = Some(e);
@@ -161,33 +354,148 @@ pub fn compile_dir_sibling(dir: &Path) -> Result<Vec<PathBuf>> {
         return Err(e);
     }
 
-    let mut module_stems: Vec<String> = outputs
-        .iter()
-        .filter_map(|o| o.file_stem().and_then(|s| s.to_str()).map(String::from))
+    write_mod_file(dir, options)?;
+    Ok(DirCompileReport {
+        outputs,
+        skipped,
+        warnings,
+    })
+}
+
+/// Rewrite `dir/mod.rs` from the `.ruitl` sources whose sibling output
+/// exists, without compiling anything. Used by watch mode after a template
+/// is added or removed. When no compiled sources remain, a generated
+/// `mod.rs` (one starting with [`GENERATED_MARKER`]) is deleted.
+pub fn write_mod_file(dir: &Path, options: &CompileOptions) -> Result<()> {
+    let mut modules: Vec<(String, Vec<String>)> = Vec::new();
+    for input in ruitl_sources(dir) {
+        let output = sibling_path(&input)?;
+        if !output.exists() {
+            continue;
+        }
+        if let Some(stem) = output.file_stem().and_then(|s| s.to_str()) {
+            modules.push((stem.to_string(), sibling_components(&input, &output)?));
+        }
+    }
+    if !modules.is_empty() {
+        return write_sibling_mod_file(dir, &mut modules, options);
+    }
+    let mod_rs = dir.join("mod.rs");
+    if let Ok(existing) = fs::read_to_string(&mod_rs) {
+        if existing.starts_with(&format!("// {}", GENERATED_MARKER)) {
+            fs::remove_file(mod_rs)?;
+        }
+    }
+    Ok(())
+}
+
+/// Every file under `dir` the compiler wrote: sibling `*_ruitl.rs` outputs
+/// carrying the `// ruitl-hash:` header, and `mod.rs` files starting with
+/// [`GENERATED_MARKER`]. Hand-written files never match, so the list is
+/// safe to delete. Sorted by path.
+pub fn generated_files(dir: &Path) -> Vec<PathBuf> {
+    let mod_header = format!("// {}", GENERATED_MARKER);
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.path().to_path_buf())
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let header = if name.ends_with("_ruitl.rs") {
+                HASH_HEADER_PREFIX
+            } else if name == "mod.rs" {
+                mod_header.as_str()
+            } else {
+                return false;
+            };
+            fs::read_to_string(path)
+                .map(|content| content.starts_with(header))
+                .unwrap_or(false)
+        })
         .collect();
-    module_stems.sort();
-    if !module_stems.is_empty() {
-        write_sibling_mod_file(dir, &module_stems)?;
+    files.sort();
+    files
+}
+
+/// Every `.ruitl` file under `dir`, in walk order.
+fn ruitl_sources(dir: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.path().is_file()
+                && e.path().extension().map(|x| x == "ruitl").unwrap_or(false)
+        })
+        .map(|e| e.path().to_path_buf())
+        .collect()
+}
+
+/// Names of the components in `ast` that `register_all` can instantiate:
+/// non-generic components that also have a template (and therefore a
+/// `Component` impl).
+fn registrable_components(ast: &RuitlFile) -> Vec<String> {
+    ast.components
+        .iter()
+        .filter(|c| c.generics.is_empty())
+        .filter(|c| ast.templates.iter().any(|t| t.name == c.name))
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+/// [`registrable_components`] for `source`, read from the header of its
+/// sibling `output`. Sources are only re-parsed for output written before
+/// that header existed.
+fn sibling_components(source: &Path, output: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(output)?;
+    let header = content
+        .lines()
+        .nth(1)
+        .and_then(|line| line.strip_prefix(COMPONENTS_HEADER_PREFIX));
+    match header {
+        Some(names) => Ok(names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect()),
+        None => Ok(registrable_components(&parse_str(
+            &fs::read_to_string(source)?,
+        )?)),
     }
-    Ok(outputs)
 }
 
-fn write_sibling_mod_file(dir: &Path, stems: &[String]) -> Result<()> {
-    let mut sorted = stems.to_vec();
-    sorted.sort();
-    let mut content = String::from(
-        "// @generated by ruitl_compiler — do not edit. Regenerated on each compile.\n\n",
-    );
-    for stem in &sorted {
+/// `modules` pairs each sibling module stem with the components it defines.
+fn write_sibling_mod_file(
+    dir: &Path,
+    modules: &mut [(String, Vec<String>)],
+    options: &CompileOptions,
+) -> Result<()> {
+    modules.sort();
+    let mut content = options.mod_file_header();
+    for (stem, _) in modules.iter() {
         content.push_str(&format!("#[allow(non_snake_case)] pub mod {};\n", stem));
     }
     content.push('\n');
-    for stem in &sorted {
+    for (stem, _) in modules.iter() {
         content.push_str(&format!(
             "#[allow(unused_imports)] pub use {}::*;\n",
             stem
         ));
     }
+    content.push_str(
+        "\n/// Register every component compiled from this directory, keyed by name.\n\
+         #[allow(dead_code, unused_variables)]\n\
+         pub fn register_all(renderer: &mut ruitl::component::ComponentRenderer) {\n",
+    );
+    for (stem, components) in modules.iter() {
+        for name in components {
+            content.push_str(&format!(
+                "    renderer.register(\"{name}\", {stem}::{name});\n"
+            ));
+        }
+    }
+    content.push_str("}\n");
     fs::write(dir.join("mod.rs"), content)?;
     Ok(())
 }
@@ -199,12 +507,12 @@ fn sanitize_stem(stem: &str) -> String {
     stem.to_string()
 }
 
-fn format_rust(raw: String) -> String {
+fn format_rust(raw: String, options: &CompileOptions) -> String {
     use std::io::Write;
     use std::process::{Command, Stdio};
 
     let child = Command::new("rustfmt")
-        .args(["--edition", "2021", "--emit", "stdout"])
+        .args(options.rustfmt_args())
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
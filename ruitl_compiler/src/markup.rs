@@ -0,0 +1,59 @@
+//! HTML rules shared by the runtime renderer and the code generator.
+//!
+//! `ruitl::html` renders element trees with these, and codegen uses the same
+//! functions when it pre-renders static markup, so a folded literal is
+//! byte-for-byte what the runtime would have produced.
+
+use std::borrow::Cow;
+
+/// Escape the five XML-significant characters (`&`, `<`, `>`, `"`, `'`).
+/// Borrows when nothing needs escaping.
+pub fn escape_html(input: &str) -> Cow<'_, str> {
+    let Some(first) = input.find(['&', '<', '>', '"', '\'']) else {
+        return Cow::Borrowed(input);
+    };
+
+    let mut out = String::with_capacity(input.len() + 16);
+    out.push_str(&input[..first]);
+    for c in input[first..].chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Whether `tag` is a void element, rendered as `<tag />` without children.
+pub fn is_void_element(tag: &str) -> bool {
+    matches!(
+        tag.to_lowercase().as_str(),
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Whether whitespace inside `tag` is content (`pre`, `textarea`) or code
+/// (`script`, `style`), so it must be written out exactly as given.
+pub fn preserves_whitespace(tag: &str) -> bool {
+    matches!(
+        tag.to_ascii_lowercase().as_str(),
+        "pre" | "textarea" | "script" | "style"
+    )
+}
@@ -29,6 +29,21 @@ pub struct PropDef {
     pub prop_type: String,
     pub optional: bool,
     pub default_value: Option<String>,
+    /// `///` doc comments above the prop, without the marker.
+    pub docs: Vec<String>,
+    /// Plain `//` / `/* */` comments above the prop, kept so `ruitl fmt`
+    /// can write them back.
+    pub comments: Vec<String>,
+    /// `@name` / `@name(args)` annotations above the prop, e.g. `@min(0)`.
+    pub annotations: Vec<PropAnnotation>,
+}
+
+/// An `@name(args)` annotation on a prop. `args` is the raw text between
+/// the parentheses, or `None` for a bare `@name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropAnnotation {
+    pub name: String,
+    pub args: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -52,6 +67,9 @@ pub struct GenericParam {
 pub struct ParamDef {
     pub name: String,
     pub param_type: String,
+    /// `name: Type = expr` — used when the matching optional prop is `None`
+    /// (or always, when no prop of that name exists).
+    pub default_value: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -81,6 +99,9 @@ pub enum TemplateAst {
     /// sparingly — caller is responsible for ensuring the expression
     /// produces safe HTML.
     RawExpression(String),
+    /// `@debug(expr)` — dumps `{:?}` of the expression as an HTML comment in
+    /// debug builds (`cfg!(debug_assertions)`); renders nothing in release.
+    Debug(String),
     /// Conditional rendering: if condition { ... } else { ... }
     If {
         condition: String,
@@ -115,6 +136,9 @@ pub enum TemplateAst {
     Fragment(Vec<TemplateAst>),
     /// Raw HTML (unescaped)
     Raw(String),
+    /// `<!-- ... -->` in a template body. Holds the text between the
+    /// markers verbatim and is emitted unchanged.
+    Comment(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -131,6 +155,9 @@ pub enum AttributeValue {
     Expression(String),
     /// Conditional attribute: disabled?={condition}
     Conditional(String),
+    /// Class directive: class:active={condition}. The owning `Attribute`'s
+    /// `name` holds the class (`active`), not the full `class:active`.
+    ClassToggle(String),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -155,6 +182,9 @@ pub struct RuitlParser {
     /// yet been attached to a declaration. The next top-level `parse_*`
     /// drains this buffer into its `leading_comments` field.
     pending_comments: Vec<String>,
+    /// Comments found between the attributes of an opening tag. The AST
+    /// has nowhere to keep them; see [`tag_comments`](Self::tag_comments).
+    tag_comments: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -184,9 +214,17 @@ impl RuitlParser {
             line: 1,
             column: 1,
             pending_comments: Vec::new(),
+            tag_comments: Vec::new(),
         }
     }
 
+    /// Comments written inside opening tags (`<div /* note */ class="x">`).
+    /// They don't reach the AST, so anything that reprints source must
+    /// refuse to drop them.
+    pub fn tag_comments(&self) -> &[String] {
+        &self.tag_comments
+    }
+
     pub fn parse(&mut self) -> Result<RuitlFile> {
         let mut components = Vec::new();
         let mut templates = Vec::new();
@@ -302,6 +340,34 @@ impl RuitlParser {
     }
 
     fn parse_prop_def(&mut self) -> Result<PropDef> {
+        let mut annotations = Vec::new();
+        while self.match_char('@') {
+            let name = self.parse_identifier()?;
+            let args = if self.match_char('(') {
+                let args = self.parse_expression_until(&[')'])?;
+                if !self.match_char(')') {
+                    return Err(self.error("Expected ')' to close prop annotation"));
+                }
+                Some(args.trim().to_string())
+            } else {
+                None
+            };
+            annotations.push(PropAnnotation { name, args });
+            self.skip_whitespace_and_comments();
+        }
+
+        // `///` lines arrive as `//` comments whose text starts with `/`.
+        // Plain comments stay with the prop rather than leaking onto the
+        // next declaration.
+        let (docs, comments): (Vec<String>, Vec<String>) = self
+            .take_pending_comments()
+            .into_iter()
+            .partition(|c| c.starts_with('/'));
+        let docs = docs
+            .into_iter()
+            .map(|d| d[1..].trim().to_string())
+            .collect();
+
         let name = self.parse_identifier()?;
 
         self.skip_whitespace();
@@ -333,6 +399,9 @@ impl RuitlParser {
             prop_type,
             optional,
             default_value,
+            docs,
+            comments,
+            annotations,
         })
     }
 
@@ -367,10 +436,26 @@ impl RuitlParser {
             self.skip_whitespace();
             let param_type = self.parse_type()?;
 
-            params.push(ParamDef {
-                name: param_name,
-                param_type,
-            });
+            self.skip_whitespace();
+            let default_value = if self.match_char('=') {
+                self.skip_whitespace();
+                Some(self.parse_expression_until(&[',', ')'])?)
+            } else {
+                None
+            };
+
+            // `context: &ComponentContext` is always passed to `render`, so
+            // accept it in the signature for readability but don't record it.
+            let implicit_context = param_name == "context"
+                && param_type.replace(' ', "") == "&ComponentContext"
+                && default_value.is_none();
+            if !implicit_context {
+                params.push(ParamDef {
+                    name: param_name,
+                    param_type,
+                    default_value,
+                });
+            }
 
             self.skip_whitespace();
             if self.match_char(',') {
@@ -525,11 +610,15 @@ impl RuitlParser {
             // Check if this is a DOCTYPE declaration
             if self.peek_string(9) == "<!DOCTYPE" {
                 self.parse_doctype()
+            } else if self.peek_string(4) == "<!--" {
+                self.parse_html_comment()
             } else {
                 self.parse_element()
             }
         } else if self.check_char('{') {
             self.parse_expression_node()
+        } else if self.peek_string(7) == "@debug(" {
+            self.parse_debug_directive()
         } else if self.check_char('@') {
             self.parse_component_invocation()
         } else if self.match_keyword("if") {
@@ -543,6 +632,22 @@ impl RuitlParser {
         }
     }
 
+    fn parse_html_comment(&mut self) -> Result<TemplateAst> {
+        let start = (self.position, self.line, self.column);
+        self.match_str("<!--");
+        let mut text = String::new();
+        while !self.match_str("-->") {
+            if self.is_at_end() {
+                // Point the error at the opening `<!--`, not at EOF.
+                (self.position, self.line, self.column) = start;
+                return Err(self.error("Unterminated HTML comment: expected '-->'"));
+            }
+            text.push(self.current_char());
+            self.advance();
+        }
+        Ok(TemplateAst::Comment(text))
+    }
+
     fn parse_element(&mut self) -> Result<TemplateAst> {
         if !self.match_char('<') {
             return Err(self.error("Expected '<' to start element"));
@@ -552,13 +657,13 @@ impl RuitlParser {
         let mut attributes = Vec::new();
         let mut self_closing = false;
 
-        self.skip_whitespace();
+        self.skip_whitespace_and_tag_comments();
 
         // Parse attributes
         while !self.check_char('>') && !self.check_char('/') && !self.is_at_end() {
             let attr = self.parse_attribute()?;
             attributes.push(attr);
-            self.skip_whitespace();
+            self.skip_whitespace_and_tag_comments();
         }
 
         // Check for self-closing
@@ -595,8 +700,11 @@ impl RuitlParser {
 
         // Parse closing tag
         self.skip_whitespace();
-        if !self.match_str(&format!("</{}>", tag)) {
+        let Some(len) = self.closing_tag_len(&tag) else {
             return Err(self.error(&format!("Expected closing tag '</{}>", tag)));
+        };
+        for _ in 0..len {
+            self.advance();
         }
 
         Ok(TemplateAst::Element {
@@ -610,6 +718,10 @@ impl RuitlParser {
     fn parse_attribute(&mut self) -> Result<Attribute> {
         let name = self.parse_attribute_name()?;
 
+        if let Some(class) = name.strip_prefix("class:") {
+            return self.parse_class_directive(class.to_string());
+        }
+
         // Check for conditional attribute (disabled?)
         let conditional = self.match_char('?');
 
@@ -644,6 +756,34 @@ impl RuitlParser {
         Ok(Attribute { name, value })
     }
 
+    /// Parse the `={condition}` tail of a `class:NAME` directive.
+    fn parse_class_directive(&mut self, class: String) -> Result<Attribute> {
+        if class.is_empty() {
+            return Err(self.error("Expected class name after 'class:'"));
+        }
+
+        self.skip_whitespace();
+        if !self.match_char('=') {
+            return Err(self.error(&format!(
+                "Class directive 'class:{}' requires a condition: class:{}={{...}}",
+                class, class
+            )));
+        }
+        self.skip_whitespace();
+        if !self.match_char('{') {
+            return Err(self.error("Expected '{' to start class directive condition"));
+        }
+        let condition = self.parse_expression_until(&['}'])?;
+        if !self.match_char('}') {
+            return Err(self.error("Expected '}' to close class directive condition"));
+        }
+
+        Ok(Attribute {
+            name: class,
+            value: AttributeValue::ClassToggle(condition),
+        })
+    }
+
     fn parse_expression_node(&mut self) -> Result<TemplateAst> {
         if !self.match_char('{') {
             return Err(self.error("Expected '{' to start expression"));
@@ -674,6 +814,23 @@ impl RuitlParser {
         }
     }
 
+    fn parse_debug_directive(&mut self) -> Result<TemplateAst> {
+        if !self.match_str("@debug(") {
+            return Err(self.error("Expected '@debug('"));
+        }
+
+        self.skip_whitespace();
+        let expr = self.parse_expression_until(&[')'])?;
+        if expr.is_empty() {
+            return Err(self.error("Expected expression inside @debug(...)"));
+        }
+        if !self.match_char(')') {
+            return Err(self.error("Expected ')' to close @debug"));
+        }
+
+        Ok(TemplateAst::Debug(expr))
+    }
+
     fn parse_component_invocation(&mut self) -> Result<TemplateAst> {
         if !self.match_char('@') {
             return Err(self.error("Expected '@' to start component invocation"));
@@ -756,8 +913,16 @@ impl RuitlParser {
         self.skip_whitespace();
         let else_branch = if self.match_keyword("else") {
             self.skip_whitespace();
+            // `else if` chains nest: the rest of the chain is the else branch.
+            if self.match_keyword("if") {
+                return Ok(TemplateAst::If {
+                    condition,
+                    then_branch,
+                    else_branch: Some(Box::new(self.parse_if_statement()?)),
+                });
+            }
             if !self.match_char('{') {
-                return Err(self.error("Expected '{' after else"));
+                return Err(self.error("Expected '{' after else or 'if' for else-if"));
             }
             let else_body = Box::new(self.parse_template_body()?);
             if !self.match_char('}') {
@@ -1011,6 +1176,13 @@ impl RuitlParser {
         while !self.is_at_end() {
             let ch = self.current_char();
 
+            // Copy string and char literals whole so a `}` or `)` inside
+            // them (`format!("{{}}")`, `'}'`) can't end the expression.
+            if ch == '"' || self.at_char_literal() {
+                self.copy_quoted(&mut expr);
+                continue;
+            }
+
             match ch {
                 '{' => {
                     if brace_depth == 0 && terminators.contains(&ch) {
@@ -1065,6 +1237,40 @@ impl RuitlParser {
         Ok(expr.trim().to_string())
     }
 
+    /// Whether a `'` at the current position opens a char literal (`'x'`,
+    /// `'\n'`) rather than a lifetime.
+    fn at_char_literal(&self) -> bool {
+        if self.current_char() != '\'' {
+            return false;
+        }
+        match self.input.get(self.position + 1) {
+            Some('\\') => true,
+            Some(_) => self.input.get(self.position + 2) == Some(&'\''),
+            None => false,
+        }
+    }
+
+    /// Append the string or char literal starting at the current quote to
+    /// `out`, through its closing quote. Backslash escapes are copied as-is.
+    fn copy_quoted(&mut self, out: &mut String) {
+        let quote = self.current_char();
+        out.push(quote);
+        self.advance();
+        while !self.is_at_end() {
+            let ch = self.current_char();
+            out.push(ch);
+            self.advance();
+            if ch == '\\' {
+                if !self.is_at_end() {
+                    out.push(self.current_char());
+                    self.advance();
+                }
+            } else if ch == quote {
+                break;
+            }
+        }
+    }
+
     fn skip_whitespace(&mut self) {
         while !self.is_at_end() && self.current_char().is_whitespace() {
             if self.current_char() == '\n' {
@@ -1100,6 +1306,16 @@ impl RuitlParser {
         }
     }
 
+    /// `skip_whitespace_and_comments` for the gaps between attributes in an
+    /// opening tag. Comments there have no declaration to attach to, so they
+    /// go to `tag_comments` rather than staying in `pending_comments`.
+    fn skip_whitespace_and_tag_comments(&mut self) {
+        let pending = self.pending_comments.len();
+        self.skip_whitespace_and_comments();
+        let dropped = self.pending_comments.drain(pending..);
+        self.tag_comments.extend(dropped);
+    }
+
     /// Drain any buffered comments. Called by each top-level parse_*
     /// so whatever the lexer has collected attaches to the next decl.
     fn take_pending_comments(&mut self) -> Vec<String> {
@@ -1286,26 +1502,32 @@ impl RuitlParser {
     }
 
     fn check_closing_tag(&self, tag: &str) -> bool {
-        let closing_tag = format!("</{}>", tag);
-        let closing_chars: Vec<char> = closing_tag.chars().collect();
-
-        // Skip whitespace to find the closing tag
-        let mut pos = self.position;
-        while pos < self.input.len() && self.input[pos].is_whitespace() {
-            pos += 1;
-        }
+        self.closing_tag_len(tag).is_some()
+    }
 
-        if pos + closing_chars.len() > self.input.len() {
-            return false;
-        }
+    /// Length of the `</tag>` at the current position, counting leading
+    /// whitespace and any whitespace before the `>` (`</div\n>`), or `None`
+    /// if the closing tag isn't next.
+    fn closing_tag_len(&self, tag: &str) -> Option<usize> {
+        let skip_ws = |mut pos: usize| {
+            while pos < self.input.len() && self.input[pos].is_whitespace() {
+                pos += 1;
+            }
+            pos
+        };
 
-        for (i, &expected_char) in closing_chars.iter().enumerate() {
-            if self.input[pos + i] != expected_char {
-                return false;
+        let mut pos = skip_ws(self.position);
+        for expected_char in format!("</{}", tag).chars() {
+            if self.input.get(pos) != Some(&expected_char) {
+                return None;
             }
+            pos += 1;
         }
-
-        true
+        pos = skip_ws(pos);
+        if self.input.get(pos) != Some(&'>') {
+            return None;
+        }
+        Some(pos + 1 - self.position)
     }
 
     fn error(&self, message: &str) -> CompileError {
@@ -1476,6 +1698,74 @@ ruitl Greeting(name: String) {
         assert_eq!(template.params[0].param_type, "String");
     }
 
+    #[test]
+    fn test_parse_template_param_defaults() {
+        let input = r#"
+ruitl Badge(label: String, tone: String = "info".to_string(), context: &ComponentContext) {
+    <span>{label}</span>
+}
+        "#;
+
+        let mut parser = RuitlParser::new(input.to_string());
+        let result = parser.parse().unwrap();
+
+        let params = &result.templates[0].params;
+        assert_eq!(params.len(), 2, "implicit context param is dropped");
+        assert_eq!(params[0].default_value, None);
+        assert_eq!(params[1].name, "tone");
+        assert_eq!(params[1].param_type, "String");
+        assert_eq!(
+            params[1].default_value.as_deref(),
+            Some(r#""info".to_string()"#)
+        );
+    }
+
+    #[test]
+    fn test_parse_prop_docs_and_annotations() {
+        let input = r#"
+// Counter.ruitl
+component Counter {
+    props {
+        /// number of items
+        @min(0) @max(max_items())
+        count: u32,
+        // not documentation
+        @deprecated
+        label: String?,
+    }
+}
+
+ruitl Counter(count: u32) { <p>{count}</p> }
+        "#;
+
+        let mut parser = RuitlParser::new(input.to_string());
+        let result = parser.parse().unwrap();
+
+        let props = &result.components[0].props;
+        assert_eq!(props[0].docs, vec!["number of items".to_string()]);
+        assert_eq!(
+            props[0].annotations,
+            vec![
+                PropAnnotation {
+                    name: "min".to_string(),
+                    args: Some("0".to_string()),
+                },
+                PropAnnotation {
+                    name: "max".to_string(),
+                    args: Some("max_items()".to_string()),
+                },
+            ]
+        );
+        assert!(props[1].docs.is_empty());
+        assert_eq!(props[1].annotations[0].name, "deprecated");
+        assert_eq!(props[1].annotations[0].args, None);
+        assert!(props[1].optional);
+        assert!(
+            result.templates[0].leading_comments.is_empty(),
+            "comments inside props must not leak onto the next declaration"
+        );
+    }
+
     #[test]
     fn test_parse_import() {
         let input = r#"import "std::collections" { HashMap, Vec }"#;
@@ -1531,6 +1821,66 @@ ruitl Greeting(name: String) {
         }
     }
 
+    #[test]
+    fn test_parse_comments_between_attributes() {
+        let input = "<div /* note */ class=\"x\" // why\n id={key} /* trailing */>hi</div>";
+        let mut parser = RuitlParser::new(input.to_string());
+
+        match parser.parse_element().unwrap() {
+            TemplateAst::Element {
+                attributes,
+                children,
+                ..
+            } => {
+                let names: Vec<&str> = attributes.iter().map(|a| a.name.as_str()).collect();
+                assert_eq!(names, vec!["class", "id"]);
+                assert_eq!(attributes[0].value, AttributeValue::Static("x".to_string()));
+                assert_eq!(children, vec![TemplateAst::Text("hi".to_string())]);
+            }
+            other => panic!("expected element, got {:?}", other),
+        }
+        assert!(parser.take_pending_comments().is_empty());
+    }
+
+    #[test]
+    fn test_parse_class_directive() {
+        let input = r#"<a class="tab" class:active={is_active}>Home</a>"#;
+
+        let mut parser = RuitlParser::new(input.to_string());
+        let result = parser.parse_element().unwrap();
+
+        let TemplateAst::Element { attributes, .. } = result else {
+            panic!("Expected element");
+        };
+        assert_eq!(attributes.len(), 2);
+        assert_eq!(attributes[1].name, "active");
+        assert_eq!(
+            attributes[1].value,
+            AttributeValue::ClassToggle("is_active".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_class_directive_requires_condition() {
+        let mut parser = RuitlParser::new(r#"<a class:active>Home</a>"#.to_string());
+        let err = parser.parse_element().unwrap_err().to_string();
+        assert!(err.contains("requires a condition"), "got: {}", err);
+    }
+
+    #[test]
+    fn test_parse_debug_directive() {
+        let mut parser = RuitlParser::new("<div>@debug(user.roles.get(0))</div>".to_string());
+        let result = parser.parse_element().unwrap();
+
+        let TemplateAst::Element { children, .. } = result else {
+            panic!("Expected element");
+        };
+        assert_eq!(
+            children,
+            vec![TemplateAst::Debug("user.roles.get(0)".to_string())]
+        );
+    }
+
     #[test]
     fn test_parse_expression() {
         let input = r#"{user.name.to_uppercase()}"#;
@@ -1646,6 +1996,83 @@ ruitl Greeting(name: String) {
         }
     }
 
+    /// Unroll an `if`/`else if` chain into its conditions, plus whether it
+    /// ends in a plain `else`.
+    fn if_chain(mut node: &TemplateAst) -> (Vec<String>, bool) {
+        let mut conditions = Vec::new();
+        loop {
+            let TemplateAst::If {
+                condition,
+                then_branch,
+                else_branch,
+            } = node
+            else {
+                return (conditions, true);
+            };
+            assert!(then_branch.is_element_with_tag("p"));
+            conditions.push(condition.clone());
+            match else_branch {
+                Some(next) => node = next,
+                None => return (conditions, false),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_else_if_chains() {
+        let two = r#"if a { <p>A</p> } else if b { <p>B</p> }"#;
+        let mut parser = RuitlParser::new(two.to_string());
+        parser.match_keyword("if");
+        let result = parser.parse_if_statement().unwrap();
+        assert_eq!(if_chain(&result), (vec!["a".into(), "b".into()], false));
+
+        let three = r#"
+if count == 0 {
+    <p>None</p>
+} else if count == 1 {
+    <p>One</p>
+} else if count < 10 {
+    <p>A few</p>
+} else {
+    <p>Many</p>
+}"#;
+        let mut parser = RuitlParser::new(three.trim_start().to_string());
+        parser.match_keyword("if");
+        let result = parser.parse_if_statement().unwrap();
+        assert_eq!(
+            if_chain(&result),
+            (
+                vec!["count == 0".into(), "count == 1".into(), "count < 10".into()],
+                true
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_html_comment_between_siblings() {
+        let input = r#"<div><p>One</p> <!-- TODO: fix layout --> <p>Two</p></div>"#;
+
+        let mut parser = RuitlParser::new(input.to_string());
+        let TemplateAst::Element { children, .. } = parser.parse_element().unwrap() else {
+            panic!("Expected element AST node");
+        };
+        let nodes: Vec<_> = children
+            .iter()
+            .filter(|c| !matches!(c, TemplateAst::Text(t) if t.trim().is_empty()))
+            .collect();
+        assert_eq!(nodes.len(), 3, "{:?}", children);
+        assert!(nodes[0].is_element_with_tag("p"));
+        assert_eq!(
+            nodes[1],
+            &TemplateAst::Comment(" TODO: fix layout ".to_string())
+        );
+        assert!(nodes[2].is_element_with_tag("p"));
+
+        let mut parser = RuitlParser::new("<div><!-- never closed </div>".to_string());
+        let err = parser.parse_element().unwrap_err().to_string();
+        assert!(err.contains("Unterminated HTML comment"), "{}", err);
+    }
+
     #[test]
     fn test_parse_for_statement() {
         let input = r#"for item in items { <li>{item}</li> }"#;
@@ -1691,6 +2118,78 @@ ruitl Greeting(name: String) {
         }
     }
 
+    #[test]
+    fn test_parse_boolean_attribute_before_self_closing_slash() {
+        for input in ["<input required/>", "<input disabled? type=\"text\" required/>"] {
+            let mut parser = RuitlParser::new(input.to_string());
+            let result = parser.parse_element().unwrap();
+
+            let TemplateAst::Element {
+                tag,
+                attributes,
+                self_closing,
+                ..
+            } = result
+            else {
+                panic!("Expected element AST node for {}", input);
+            };
+            assert_eq!(tag, "input");
+            assert!(self_closing, "{}", input);
+            let required = attributes.last().unwrap();
+            assert_eq!(required.name, "required");
+            assert_eq!(required.value, AttributeValue::Static("true".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_parse_multiline_attribute_expression_before_newline_close() {
+        let input = r#"<div
+    class={format!(
+        "card {} {}",
+        variant,
+        if active { "on" } else { "off" },
+    )}
+    data-brace={'}'}
+    title={format!("{{{}}}", name)}
+>
+    <br
+    />
+    hi
+</div
+>"#;
+        let mut parser = RuitlParser::new(input.to_string());
+
+        let TemplateAst::Element {
+            attributes,
+            children,
+            ..
+        } = parser.parse_element().unwrap()
+        else {
+            panic!("Expected element AST node");
+        };
+        let AttributeValue::Expression(class) = &attributes[0].value else {
+            panic!(
+                "Expected expression attribute, got {:?}",
+                attributes[0].value
+            );
+        };
+        assert!(class.starts_with("format!(\n"), "{}", class);
+        assert!(class.ends_with("},\n    )"), "{}", class);
+        assert_eq!(
+            attributes[1].value,
+            AttributeValue::Expression("'}'".to_string())
+        );
+        assert_eq!(
+            attributes[2].value,
+            AttributeValue::Expression("format!(\"{{{}}}\", name)".to_string())
+        );
+        assert!(matches!(
+            &children[0],
+            TemplateAst::Element { tag, self_closing: true, .. } if tag == "br"
+        ));
+        assert!(parser.is_at_end());
+    }
+
     #[test]
     fn test_parse_complex_template() {
         let input = r#"
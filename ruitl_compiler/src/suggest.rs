@@ -23,7 +23,7 @@ pub fn threshold_for(ident: &str) -> usize {
     if len <= 4 {
         2
     } else {
-        (len / 3).min(3).max(1)
+        (len / 3).clamp(1, 3)
     }
 }
 
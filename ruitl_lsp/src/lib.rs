@@ -334,12 +334,10 @@ fn position_to_offset(text: &str, pos: Position) -> Option<usize> {
     for (idx, c) in text.char_indices() {
         if line == pos.line {
             // We're on the target line. Walk forward `pos.character` chars.
-            let mut char_count = 0u32;
-            for (jdx, _) in text[idx..].char_indices() {
-                if char_count == pos.character {
+            for (char_count, (jdx, _)) in text[idx..].char_indices().enumerate() {
+                if char_count as u32 == pos.character {
                     return Some(idx + jdx);
                 }
-                char_count += 1;
             }
             // End of line reached before hitting the target column.
             return Some(text.len());
@@ -782,6 +780,9 @@ mod tests {
                 prop_type: "String".to_string(),
                 optional: false,
                 default_value: None,
+                docs: Vec::new(),
+                comments: Vec::new(),
+                annotations: Vec::new(),
             }],
             decl_position: (0, 10),
         };
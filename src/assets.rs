@@ -0,0 +1,143 @@
+//! Serving precompressed static assets.
+//!
+//! Build pipelines often write `app.js.br` / `app.js.gz` next to `app.js`.
+//! [`resolve_precompressed`] picks the sibling the client's
+//! `Accept-Encoding` allows, falling back to the plain file:
+//!
+//! ```ignore
+//! let asset = ruitl::assets::resolve_precompressed(Path::new("static/app.js"), accept);
+//! let body = std::fs::read(&asset.path)?;
+//! // Content-Type from the original path; Content-Encoding from the asset.
+//! ```
+
+use std::path::{Path, PathBuf};
+
+/// Content codings we look for on disk, with their file suffix. On equal
+/// q-values the first one wins, so brotli is preferred over gzip.
+const PRECOMPRESSED: [(&str, &str); 2] = [("br", "br"), ("gzip", "gz")];
+
+/// The file to send for a static asset request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticAsset {
+    /// File to read: the plain path or its `.br` / `.gz` sibling
+    pub path: PathBuf,
+    /// Value for the `Content-Encoding` header, when a sibling was picked
+    pub content_encoding: Option<&'static str>,
+}
+
+/// q-value the `Accept-Encoding` header gives `coding`, counting a `*` entry
+/// for codings not listed by name. `None` when the header doesn't mention it.
+fn quality(accept_encoding: &str, coding: &str) -> Option<f32> {
+    let mut wildcard = None;
+    for entry in accept_encoding.split(',') {
+        let mut parts = entry.split(';');
+        let name = parts.next().unwrap_or("").trim();
+        let q = parts
+            .filter_map(|param| param.trim().strip_prefix("q="))
+            .find_map(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if name.eq_ignore_ascii_case(coding) {
+            return Some(q);
+        }
+        if name == "*" {
+            wildcard = Some(q);
+        }
+    }
+    wildcard
+}
+
+/// The coding from `candidates` that `accept_encoding` ranks highest,
+/// skipping any it refuses with `q=0`. Ties go to the earlier candidate.
+pub fn best_encoding(accept_encoding: &str, candidates: &[&'static str]) -> Option<&'static str> {
+    let mut best: Option<(&'static str, f32)> = None;
+    for &coding in candidates {
+        let Some(q) = quality(accept_encoding, coding) else {
+            continue;
+        };
+        if q > 0.0 && !matches!(best, Some((_, best_q)) if best_q >= q) {
+            best = Some((coding, q));
+        }
+    }
+    best.map(|(coding, _)| coding)
+}
+
+/// Pick the `.br` or `.gz` sibling of `path` when it exists and
+/// `accept_encoding` allows it, otherwise `path` itself. Only siblings that
+/// exist on disk are considered.
+pub fn resolve_precompressed(path: &Path, accept_encoding: Option<&str>) -> StaticAsset {
+    let plain = StaticAsset {
+        path: path.to_path_buf(),
+        content_encoding: None,
+    };
+    let Some(accept_encoding) = accept_encoding else {
+        return plain;
+    };
+
+    let sibling = |suffix: &str| {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(".");
+        name.push(suffix);
+        PathBuf::from(name)
+    };
+    let available: Vec<&'static str> = PRECOMPRESSED
+        .iter()
+        .filter(|(_, suffix)| sibling(suffix).is_file())
+        .map(|(coding, _)| *coding)
+        .collect();
+
+    match best_encoding(accept_encoding, &available) {
+        Some(coding) => {
+            let suffix = PRECOMPRESSED
+                .iter()
+                .find(|(c, _)| *c == coding)
+                .map(|(_, suffix)| *suffix)
+                .unwrap_or(coding);
+            StaticAsset {
+                path: sibling(suffix),
+                content_encoding: Some(coding),
+            }
+        }
+        None => plain,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_best_encoding_honours_q_values() {
+        let both = ["br", "gzip"];
+        assert_eq!(best_encoding("gzip, deflate, br", &both), Some("br"));
+        assert_eq!(best_encoding("br;q=0.5, gzip", &both), Some("gzip"));
+        assert_eq!(best_encoding("br;q=0, gzip;q=0", &both), None);
+        assert_eq!(best_encoding("*", &both), Some("br"));
+        assert_eq!(best_encoding("*;q=0, gzip", &both), Some("gzip"));
+        assert_eq!(best_encoding("identity", &both), None);
+    }
+
+    #[test]
+    fn test_resolve_precompressed_prefers_existing_sibling() {
+        let dir = tempdir().unwrap();
+        let app = dir.path().join("app.js");
+        fs::write(&app, "plain").unwrap();
+
+        let plain = resolve_precompressed(&app, Some("br, gzip"));
+        assert_eq!(plain.path, app);
+        assert_eq!(plain.content_encoding, None);
+
+        fs::write(dir.path().join("app.js.br"), "brotli").unwrap();
+        let brotli = resolve_precompressed(&app, Some("gzip, br"));
+        assert_eq!(brotli.path, dir.path().join("app.js.br"));
+        assert_eq!(brotli.content_encoding, Some("br"));
+
+        fs::write(dir.path().join("app.js.gz"), "gzip").unwrap();
+        let gzip = resolve_precompressed(&app, Some("gzip"));
+        assert_eq!(gzip.path, dir.path().join("app.js.gz"));
+        assert_eq!(gzip.content_encoding, Some("gzip"));
+
+        assert_eq!(resolve_precompressed(&app, None).path, app);
+    }
+}
@@ -0,0 +1,121 @@
+//! Optional `axum` integration.
+//!
+//! With the `axum` feature enabled, a rendered [`Html`] tree can be returned
+//! directly from an axum handler, and a [`ComponentContext`] can be taken as
+//! an extractor (path, query string and headers are copied from the request):
+//!
+//! ```ignore
+//! use axum::{routing::get, Router};
+//! use ruitl::prelude::*;
+//!
+//! async fn hello(ctx: ComponentContext) -> ruitl::Result<Html> {
+//!     Hello.render(&HelloProps { name: "World".into() }, &ctx)
+//! }
+//!
+//! let app: Router = Router::new().route("/", get(hello));
+//! ```
+
+use ::axum::async_trait;
+use ::axum::extract::FromRequestParts;
+use ::axum::http::{header, request::Parts, StatusCode};
+use ::axum::response::{IntoResponse, Response};
+use std::convert::Infallible;
+
+use crate::component::ComponentContext;
+use crate::error::RuitlError;
+use crate::html::Html;
+
+/// `Content-Type` sent with every rendered page.
+pub const HTML_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+impl IntoResponse for Html {
+    fn into_response(self) -> Response {
+        ([(header::CONTENT_TYPE, HTML_CONTENT_TYPE)], self.render()).into_response()
+    }
+}
+
+/// Render failures surface as a plain-text 500 so `Result<Html>` handlers
+/// work with `?`.
+impl IntoResponse for RuitlError {
+    fn into_response(self) -> Response {
+        (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()).into_response()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ComponentContext
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let mut context = ComponentContext::new().with_path(parts.uri.path());
+
+        if let Some(query) = parts.uri.query() {
+            for pair in query.split('&').filter(|p| !p.is_empty()) {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                context = context.with_query(decode_component(key), decode_component(value));
+            }
+        }
+
+        for (name, value) in &parts.headers {
+            if let Ok(value) = value.to_str() {
+                context = context.with_header(name.as_str(), value);
+            }
+        }
+
+        Ok(context)
+    }
+}
+
+/// Percent-decode a query-string component, treating `+` as a space.
+fn decode_component(raw: &str) -> String {
+    let spaced = raw.replace('+', " ");
+    urlencoding::decode(&spaced)
+        .map(|s| s.into_owned())
+        .unwrap_or(spaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::axum::http::Request;
+
+    #[test]
+    fn test_html_into_response_sets_content_type() {
+        let html = Html::text("hi & bye");
+        let response = html.into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            HTML_CONTENT_TYPE
+        );
+    }
+
+    #[test]
+    fn test_error_into_response_is_server_error() {
+        let response = RuitlError::render("boom").into_response();
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_context_extractor_copies_request_parts() {
+        let request = Request::builder()
+            .uri("/users?name=Ada+Lovelace&tab=profile%20info")
+            .header("x-request-id", "abc")
+            .body(())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+
+        let context = ComponentContext::from_request_parts(&mut parts, &())
+            .await
+            .unwrap();
+
+        assert_eq!(context.path.as_deref(), Some("/users"));
+        assert_eq!(context.get_query("name"), Some(&"Ada Lovelace".to_string()));
+        assert_eq!(context.get_query("tab"), Some(&"profile info".to_string()));
+        assert_eq!(context.get_header("x-request-id"), Some(&"abc".to_string()));
+    }
+}
@@ -20,24 +20,31 @@
 //! });
 //! ```
 
-use crate::config::{RouteConfig, RuitlConfig};
+use crate::config::{RouteConfig, RuitlConfig, UrlStyle};
 use crate::error::{Result, RuitlError};
+use crate::html::rebase_rendered_urls;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Map a URL path to a filesystem path under `out_dir`. `/` and empty paths
 /// resolve to `index.html`; every other path becomes `<stripped>/index.html`
-/// so it serves at its URL from any plain static-file server.
-fn route_to_file(out_dir: &Path, url_path: &str) -> PathBuf {
+/// (so it serves at its URL from any plain static-file server) or
+/// `<stripped>.html`, depending on `style`.
+fn route_to_file(out_dir: &Path, url_path: &str, style: UrlStyle) -> PathBuf {
     let trimmed = url_path.trim_matches('/');
     if trimmed.is_empty() {
         return out_dir.join("index.html");
     }
-    out_dir.join(trimmed).join("index.html")
+    match style {
+        UrlStyle::DirectoryIndex => out_dir.join(trimmed).join("index.html"),
+        UrlStyle::HtmlSuffix => out_dir.join(format!("{}.html", trimmed)),
+    }
 }
 
 /// Render every route listed in the config using the caller-provided
-/// dispatcher. On success returns the list of files written.
+/// dispatcher. When `build.base_url` is set, root-relative links in each
+/// page are prefixed with its path. On success returns the list of files
+/// written.
 pub fn render_site<F>(cfg: &RuitlConfig, out_dir: &Path, mut renderer: F) -> Result<Vec<PathBuf>>
 where
     F: FnMut(&str, &str) -> Result<String>,
@@ -45,27 +52,34 @@ where
     fs::create_dir_all(out_dir)
         .map_err(|e| RuitlError::config(format!("create out dir {}: {}", out_dir.display(), e)))?;
 
+    let base_url = cfg.build.base_url.as_deref();
     let mut written = Vec::with_capacity(cfg.routes.len());
     for route in &cfg.routes {
-        let output = render_route(route, out_dir, &mut renderer)?;
+        let props_file = cfg.resolve_path(&route.props_file);
+        let target = route_to_file(out_dir, &route.path, cfg.build.url_style);
+        let output = render_route(route, &props_file, target, base_url, &mut renderer)?;
         written.push(output);
     }
     Ok(written)
 }
 
-fn render_route<F>(route: &RouteConfig, out_dir: &Path, renderer: &mut F) -> Result<PathBuf>
+fn render_route<F>(
+    route: &RouteConfig,
+    props_file: &Path,
+    target: PathBuf,
+    base_url: Option<&str>,
+    renderer: &mut F,
+) -> Result<PathBuf>
 where
     F: FnMut(&str, &str) -> Result<String>,
 {
-    let props_json = fs::read_to_string(&route.props_file).map_err(|e| {
-        RuitlError::config(format!(
-            "read props file {}: {}",
-            route.props_file.display(),
-            e
-        ))
+    let props_json = fs::read_to_string(props_file).map_err(|e| {
+        RuitlError::config(format!("read props file {}: {}", props_file.display(), e))
     })?;
-    let html = renderer(&route.component, &props_json)?;
-    let target = route_to_file(out_dir, &route.path);
+    let mut html = renderer(&route.component, &props_json)?;
+    if let Some(base_url) = base_url {
+        html = rebase_rendered_urls(&html, base_url);
+    }
     if let Some(parent) = target.parent() {
         fs::create_dir_all(parent).map_err(|e| {
             RuitlError::config(format!("create {}: {}", parent.display(), e))
@@ -82,13 +96,66 @@ mod tests {
 
     #[test]
     fn route_to_file_maps_root_to_index() {
-        let got = route_to_file(Path::new("/dist"), "/");
-        assert_eq!(got, PathBuf::from("/dist/index.html"));
+        for style in [UrlStyle::DirectoryIndex, UrlStyle::HtmlSuffix] {
+            let got = route_to_file(Path::new("/dist"), "/", style);
+            assert_eq!(got, PathBuf::from("/dist/index.html"));
+        }
     }
 
     #[test]
     fn route_to_file_preserves_nested_path() {
-        let got = route_to_file(Path::new("/dist"), "/blog/post");
+        let got = route_to_file(Path::new("/dist"), "/blog/post", UrlStyle::DirectoryIndex);
         assert_eq!(got, PathBuf::from("/dist/blog/post/index.html"));
     }
+
+    #[test]
+    fn route_to_file_html_suffix() {
+        let got = route_to_file(Path::new("/dist"), "/blog/post/", UrlStyle::HtmlSuffix);
+        assert_eq!(got, PathBuf::from("/dist/blog/post.html"));
+    }
+
+    #[test]
+    fn url_style_reads_from_toml() {
+        let cfg: RuitlConfig = toml::from_str(
+            r#"
+            [project]
+            name = "site"
+            version = "0.1.0"
+            authors = []
+
+            [build]
+            template_dir = "templates"
+            out_dir = "generated"
+            src_dir = "src"
+            url_style = "html_suffix"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(cfg.build.url_style, UrlStyle::HtmlSuffix);
+    }
+
+    #[test]
+    fn render_site_prefixes_links_with_base_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let props = dir.path().join("props.json");
+        fs::write(&props, "{}").unwrap();
+
+        let mut cfg = RuitlConfig::default();
+        cfg.build.base_url = Some("https://host/docs/".to_string());
+        cfg.routes.push(RouteConfig {
+            path: "/".to_string(),
+            component: "Home".to_string(),
+            props_file: props,
+        });
+
+        let out = dir.path().join("dist");
+        let written = render_site(&cfg, &out, |_, _| {
+            Ok(r#"<link href="/static/app.css" /><script src="https://cdn/x"></script>"#.into())
+        })
+        .unwrap();
+
+        let html = fs::read_to_string(&written[0]).unwrap();
+        assert!(html.contains(r#"href="/docs/static/app.css""#));
+        assert!(html.contains(r#"src="https://cdn/x""#));
+    }
 }
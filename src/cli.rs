@@ -6,6 +6,7 @@ use crate::config::RuitlConfig;
 use crate::error::{Result, RuitlError};
 use clap::{Parser, Subcommand};
 use colored::*;
+use std::cell::RefCell;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -72,6 +73,34 @@ pub enum Commands {
         #[arg(short, long, default_value = "ruitl.toml")]
         config: PathBuf,
     },
+    /// Parse and generate code for every `.ruitl` file without writing
+    /// anything, printing OK/FAIL per file. Exits non-zero if any file fails
+    /// (useful in CI and pre-commit hooks).
+    Check {
+        /// Source directory containing .ruitl files
+        #[arg(short, long, default_value = "templates")]
+        src_dir: PathBuf,
+    },
+    /// Delete the generated `*_ruitl.rs` files and `mod.rs` under a template
+    /// directory, e.g. after renaming or removing templates. Only files the
+    /// compiler wrote (recognized by their generated header) are touched.
+    Clean {
+        /// Source directory containing .ruitl files and their generated output
+        #[arg(short, long, default_value = "templates")]
+        src_dir: PathBuf,
+        /// List the files that would be removed, without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Run a named command from the `[scripts]` table in `ruitl.toml`. The
+    /// command runs through the system shell from the config's directory.
+    Run {
+        /// Script name
+        name: String,
+        /// Config file path. Defaults to `ruitl.toml` in the current directory.
+        #[arg(short, long, default_value = "ruitl.toml")]
+        config: PathBuf,
+    },
     /// Generate a scaffold project structure with example components
     Scaffold {
         /// Project name
@@ -86,6 +115,10 @@ pub enum Commands {
         /// Include example components
         #[arg(long, default_value = "true")]
         with_examples: bool,
+        /// Print the files and directories that would be created, without
+        /// writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Run the development server: watch `.ruitl` files and serve a sidecar
     /// SSE endpoint that browsers can subscribe to for auto-reload after
@@ -98,6 +131,10 @@ pub enum Commands {
         /// Port for the reload sidecar (SSE + reload.js). Default 35729.
         #[arg(long, default_value_t = 35729)]
         reload_port: u16,
+        /// Seconds an SSE stream may sit idle before a keep-alive comment is
+        /// sent. Default 30; must be at least 1.
+        #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+        heartbeat_secs: u64,
     },
     /// Show version information
     Version,
@@ -106,16 +143,106 @@ pub enum Commands {
 /// CLI application runner
 pub struct CliApp {
     verbose: bool,
+    /// Header and `rustfmt` edition for generated code, from `[build]`.
+    compile_options: ruitl_compiler::CompileOptions,
 }
 
 /// A minimal `Send`-able logger used inside the watch-mode callback. The
 /// `hotwatch` watcher moves its handler onto a background thread, so we
 /// cannot capture `&CliApp` directly.
+#[cfg(feature = "dev")]
 #[derive(Clone)]
 struct WatchLogger {
     verbose: bool,
 }
 
+/// Destination for `scaffold` output: the filesystem, or for `--dry-run` a
+/// record of each directory and file (with its size) that would be made.
+struct ScaffoldSink {
+    dry_run: bool,
+    planned: RefCell<Vec<(PathBuf, Option<usize>)>>,
+}
+
+impl ScaffoldSink {
+    fn new(dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            planned: RefCell::new(Vec::new()),
+        }
+    }
+
+    fn create_dir(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        if self.dry_run {
+            self.planned
+                .borrow_mut()
+                .push((path.as_ref().to_path_buf(), None));
+            return Ok(());
+        }
+        fs::create_dir_all(path)
+    }
+
+    fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+        if self.dry_run {
+            let size = contents.as_ref().len();
+            self.planned
+                .borrow_mut()
+                .push((path.as_ref().to_path_buf(), Some(size)));
+            return Ok(());
+        }
+        fs::write(path, contents)
+    }
+
+    /// Record the sibling `*_ruitl.rs` files and `mod.rs` that compiling the
+    /// planned templates under `dir` would write. Their sizes aren't known
+    /// until codegen runs.
+    fn plan_compiled_templates(&self, dir: &Path) {
+        let sources: Vec<PathBuf> = self
+            .planned
+            .borrow()
+            .iter()
+            .filter(|(path, size)| {
+                size.is_some()
+                    && path.starts_with(dir)
+                    && path.extension().is_some_and(|e| e == "ruitl")
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        let mut planned = self.planned.borrow_mut();
+        for source in &sources {
+            if let Ok(out) = ruitl_compiler::sibling_path(source) {
+                planned.push((out, None));
+            }
+        }
+        if !sources.is_empty() {
+            planned.push((dir.join("mod.rs"), None));
+        }
+    }
+
+    /// One sorted line per planned path, relative to `root`. Directories
+    /// end in `/`; files show their size, or `(generated)` for compiler
+    /// output.
+    fn plan_lines(&self, root: &Path) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .planned
+            .borrow()
+            .iter()
+            .map(|(path, size)| {
+                let rel = path.strip_prefix(root).unwrap_or(path).display();
+                match size {
+                    Some(bytes) => format!("{} ({} bytes)", rel, bytes),
+                    None if path.extension().is_some_and(|e| e == "rs") => {
+                        format!("{} (generated)", rel)
+                    }
+                    None => format!("{}/", rel),
+                }
+            })
+            .collect();
+        lines.sort();
+        lines.dedup();
+        lines
+    }
+}
+
 #[cfg(feature = "dev")]
 impl WatchLogger {
     fn info(&self, message: &str) {
@@ -131,10 +258,48 @@ impl WatchLogger {
     }
 }
 
+/// Apply one watch event: drop the sibling output of a `removed` template,
+/// compile the `changed` one on its own, and rewrite `mod.rs` if the set of
+/// templates changed. `mod.rs` is rewritten even when the compile fails so
+/// it never names a deleted module.
+#[cfg(feature = "dev")]
+fn recompile_changed(
+    src_dir: &Path,
+    removed: Option<&PathBuf>,
+    changed: Option<&PathBuf>,
+    layout_changed: bool,
+    options: &ruitl_compiler::CompileOptions,
+    log: &WatchLogger,
+) -> ruitl_compiler::Result<()> {
+    if let Some(path) = removed {
+        let sibling = ruitl_compiler::sibling_path(path)?;
+        if sibling.exists() {
+            fs::remove_file(&sibling)?;
+            log.info(&format!("Removed {}", sibling.display()));
+        }
+    }
+    let compiled = changed.map(|path| {
+        log.info(&format!("Change detected in {} — recompiling...", path.display()));
+        ruitl_compiler::compile_file_sibling_with(path, options)
+    });
+    if layout_changed {
+        ruitl_compiler::write_mod_file(src_dir, options)?;
+    }
+    match compiled {
+        Some(Ok(out)) => log.success(&format!("✓ Recompiled {}", out.display())),
+        Some(Err(e)) => return Err(e),
+        None => log.success("✓ Updated mod.rs"),
+    }
+    Ok(())
+}
+
 impl CliApp {
     /// Create a new CLI application
-    pub fn new(_config: RuitlConfig, verbose: bool) -> Self {
-        Self { verbose }
+    pub fn new(config: RuitlConfig, verbose: bool) -> Self {
+        Self {
+            verbose,
+            compile_options: config.build.compile_options(),
+        }
     }
 
     /// Run the CLI application
@@ -153,19 +318,27 @@ impl CliApp {
             }
             Commands::Fmt { paths, check } => self.fmt_paths(&paths, check),
             Commands::ValidateRoutes { config } => self.validate_routes(&config),
+            Commands::Check { src_dir } => self.check_templates(&src_dir),
+            Commands::Clean { src_dir, dry_run } => self.clean_generated(&src_dir, dry_run),
+            Commands::Run { name, config } => self.run_script(&name, &config),
             Commands::Scaffold {
                 name,
                 target,
                 with_server,
                 with_examples,
+                dry_run,
             } => {
-                self.scaffold_project(&name, &target, with_server, with_examples)
+                self.scaffold_project(&name, &target, with_server, with_examples, dry_run)
                     .await
             }
             Commands::Dev {
                 src_dir,
                 reload_port,
-            } => self.run_dev(&src_dir, reload_port).await,
+                heartbeat_secs,
+            } => {
+                let heartbeat = std::time::Duration::from_secs(heartbeat_secs);
+                self.run_dev(&src_dir, reload_port, heartbeat).await
+            }
             Commands::Version => {
                 println!("RUITL {}", env!("CARGO_PKG_VERSION"));
                 Ok(())
@@ -192,9 +365,22 @@ impl CliApp {
             // *_ruitl.rs files, and emits an auto-generated mod.rs that
             // re-exports each. CLI and build.rs share this entry point so
             // their output is identical.
-            let written = ruitl_compiler::compile_dir_sibling(src_dir).map_err(|e| {
-                RuitlError::generic(format!("Failed to compile templates: {}", e))
-            })?;
+            let report =
+                ruitl_compiler::compile_dir_sibling_report_with(src_dir, &self.compile_options)
+                    .map_err(|e| {
+                        RuitlError::generic(format!("Failed to compile templates: {}", e))
+                    })?;
+            let written = report.outputs;
+
+            for skipped in &report.skipped {
+                self.log_warning(&format!(
+                    "Skipped {}: no component or template definitions",
+                    skipped.display()
+                ));
+            }
+            for warning in &report.warnings {
+                self.log_warning(warning);
+            }
 
             if self.verbose {
                 for out in &written {
@@ -219,7 +405,12 @@ impl CliApp {
     /// Delegates to `ruitl::dev::run_dev`. Requires the `dev` + `server`
     /// feature combo; returns a clear error otherwise.
     #[cfg(all(feature = "dev", feature = "server"))]
-    async fn run_dev(&self, src_dir: &Path, reload_port: u16) -> Result<()> {
+    async fn run_dev(
+        &self,
+        src_dir: &Path,
+        reload_port: u16,
+        heartbeat: std::time::Duration,
+    ) -> Result<()> {
         if !src_dir.exists() {
             return Err(RuitlError::config(format!(
                 "Source directory '{}' does not exist",
@@ -231,19 +422,120 @@ impl CliApp {
             crate::dev::DevOptions {
                 reload_port,
                 verbose: self.verbose,
+                heartbeat,
             },
         )
         .await
     }
 
     #[cfg(not(all(feature = "dev", feature = "server")))]
-    async fn run_dev(&self, _src_dir: &Path, _reload_port: u16) -> Result<()> {
+    async fn run_dev(
+        &self,
+        _src_dir: &Path,
+        _reload_port: u16,
+        _heartbeat: std::time::Duration,
+    ) -> Result<()> {
         Err(RuitlError::generic(
             "`ruitl dev` requires both the 'dev' and 'server' features (enabled by default). \
              Rebuild without --no-default-features, or pass --features dev,server.",
         ))
     }
 
+    /// Run the parser and code generator over every `.ruitl` file under
+    /// `src_dir`, reporting each file and failing if any did. Nothing is
+    /// written to disk.
+    fn check_templates(&self, src_dir: &Path) -> Result<()> {
+        if !src_dir.exists() {
+            return Err(RuitlError::config(format!(
+                "Source directory '{}' does not exist",
+                src_dir.display()
+            )));
+        }
+
+        let mut files: Vec<PathBuf> = walkdir::WalkDir::new(src_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .map(|e| e.path().to_path_buf())
+            .filter(|p| p.is_file() && p.extension().map(|e| e == "ruitl").unwrap_or(false))
+            .collect();
+        files.sort();
+
+        let mut failed = 0usize;
+        for file in &files {
+            let checked = fs::read_to_string(file)
+                .map_err(ruitl_compiler::CompileError::from)
+                .and_then(|src| ruitl_compiler::parse_str(&src))
+                .and_then(|ast| {
+                    let mut generator = ruitl_compiler::CodeGenerator::new(ast)
+                        .with_lint_inline_handlers(self.compile_options.lint_inline_handlers);
+                    generator.generate()?;
+                    Ok(generator.warnings())
+                });
+            match checked {
+                Ok(warnings) => {
+                    println!("{} {}", "OK".green().bold(), file.display());
+                    for warning in warnings {
+                        self.log_warning(&warning);
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{} {}", "FAIL".red().bold(), file.display());
+                    for line in e.to_string().lines() {
+                        println!("    {}", line);
+                    }
+                }
+            }
+        }
+
+        if failed > 0 {
+            return Err(RuitlError::template(format!(
+                "{} of {} template(s) failed to check",
+                failed,
+                files.len()
+            )));
+        }
+        self.log_success(&format!("✓ {} template(s) OK", files.len()));
+        Ok(())
+    }
+
+    /// Remove the compiler's output under `src_dir`, or with `dry_run` just
+    /// list it.
+    fn clean_generated(&self, src_dir: &Path, dry_run: bool) -> Result<()> {
+        if !src_dir.exists() {
+            return Err(RuitlError::config(format!(
+                "Source directory '{}' does not exist",
+                src_dir.display()
+            )));
+        }
+
+        let files = ruitl_compiler::generated_files(src_dir);
+        if files.is_empty() {
+            self.log_success(&format!("✓ No generated files in {}", src_dir.display()));
+            return Ok(());
+        }
+
+        if dry_run {
+            println!("Would remove from {}:", src_dir.display());
+            for file in &files {
+                println!("  {}", file.strip_prefix(src_dir).unwrap_or(file).display());
+            }
+            return Ok(());
+        }
+
+        for file in &files {
+            fs::remove_file(file)
+                .map_err(|e| RuitlError::generic(format!("Remove {}: {}", file.display(), e)))?;
+            self.log_info(&format!("Removed {}", file.display()));
+        }
+        self.log_success(&format!(
+            "✓ Removed {} generated file(s) from {}",
+            files.len(),
+            src_dir.display()
+        ));
+        Ok(())
+    }
+
     /// Parse every `.ruitl` file under `src_dir` and write its AST in
     /// human-readable `{:#?}` form to a sibling `<stem>.ast.txt`. Skips
     /// codegen entirely — purely a debugging aid for authors diagnosing
@@ -320,20 +612,24 @@ impl CliApp {
         }
 
         let mut changed: Vec<PathBuf> = Vec::new();
-        let mut errors: Vec<(PathBuf, String)> = Vec::new();
+        let mut errors: Vec<RuitlError> = Vec::new();
 
         for file in &files {
             let src = match std::fs::read_to_string(file) {
                 Ok(s) => s,
                 Err(e) => {
-                    errors.push((file.clone(), format!("read: {}", e)));
+                    errors.push(RuitlError::generic(format!(
+                        "{}: read: {}",
+                        file.display(),
+                        e
+                    )));
                     continue;
                 }
             };
             let formatted = match ruitl_compiler::format::format_source(&src) {
                 Ok(s) => s,
                 Err(e) => {
-                    errors.push((file.clone(), format!("parse: {}", e)));
+                    errors.push(RuitlError::parse(format!("{}: {}", file.display(), e)));
                     continue;
                 }
             };
@@ -341,7 +637,11 @@ impl CliApp {
                 changed.push(file.clone());
                 if !check {
                     if let Err(e) = std::fs::write(file, &formatted) {
-                        errors.push((file.clone(), format!("write: {}", e)));
+                        errors.push(RuitlError::generic(format!(
+                            "{}: write: {}",
+                            file.display(),
+                            e
+                        )));
                     } else if self.verbose {
                         self.log_info(&format!("Formatted {}", file.display()));
                     }
@@ -350,13 +650,7 @@ impl CliApp {
         }
 
         if !errors.is_empty() {
-            for (p, e) in &errors {
-                self.log_warning(&format!("{}: {}", p.display(), e));
-            }
-            return Err(RuitlError::generic(format!(
-                "fmt: {} file(s) failed",
-                errors.len()
-            )));
+            return Err(RuitlError::multiple(errors));
         }
 
         if check {
@@ -385,6 +679,55 @@ impl CliApp {
         }
     }
 
+    /// Run the `[scripts]` entry called `name` through the system shell,
+    /// failing if the name is unknown or the command exits unsuccessfully.
+    fn run_script(&self, name: &str, config_path: &Path) -> Result<()> {
+        let cfg = RuitlConfig::from_file(config_path)?;
+        let command = cfg.scripts.get(name).ok_or_else(|| {
+            let mut known: Vec<&str> = cfg.scripts.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            RuitlError::config(format!(
+                "unknown script `{}` (available: {})",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            ))
+        })?;
+
+        self.log_info(&format!("Running `{}`: {}", name, command));
+        let mut shell = if cfg!(windows) {
+            let mut shell = std::process::Command::new("cmd");
+            shell.arg("/C");
+            shell
+        } else {
+            let mut shell = std::process::Command::new("sh");
+            shell.arg("-c");
+            shell
+        };
+        shell.arg(command);
+        if let Some(root) = cfg
+            .root
+            .as_deref()
+            .filter(|root| !root.as_os_str().is_empty())
+        {
+            shell.current_dir(root);
+        }
+
+        let status = shell
+            .status()
+            .map_err(|e| RuitlError::config(format!("failed to run script `{}`: {}", name, e)))?;
+        if !status.success() {
+            return Err(RuitlError::config(format!(
+                "script `{}` exited with {}",
+                name, status
+            )));
+        }
+        Ok(())
+    }
+
     /// Validate the `[[routes]]` section of a `ruitl.toml` configuration.
     ///
     /// Checks each route's `props_file` actually exists on disk and that no
@@ -400,18 +743,22 @@ impl CliApp {
         }
 
         let mut seen_paths: HashSet<String> = HashSet::new();
-        let mut errors: Vec<String> = Vec::new();
+        let mut errors: Vec<RuitlError> = Vec::new();
 
         for route in &cfg.routes {
             if !seen_paths.insert(route.path.clone()) {
-                errors.push(format!("duplicate route path `{}`", route.path));
+                errors.push(RuitlError::config(format!(
+                    "duplicate route path `{}`",
+                    route.path
+                )));
             }
-            if !route.props_file.exists() {
-                errors.push(format!(
+            let props_file = cfg.resolve_path(&route.props_file);
+            if !props_file.exists() {
+                errors.push(RuitlError::config(format!(
                     "route `{}` references missing props_file `{}`",
                     route.path,
-                    route.props_file.display()
-                ));
+                    props_file.display()
+                )));
             }
         }
 
@@ -423,20 +770,16 @@ impl CliApp {
             ));
             Ok(())
         } else {
-            for err in &errors {
-                self.log_warning(err);
-            }
-            Err(RuitlError::config(format!(
-                "Route validation failed with {} error(s)",
-                errors.len()
-            )))
+            Err(RuitlError::multiple(errors))
         }
     }
 
-    /// Enter a file-watch loop that re-runs `compile_once` when any `.ruitl`
-    /// file under `src_dir` changes. Gated on the `dev` feature (`hotwatch`
-    /// is an optional dependency). When the feature is off, returns a clear
-    /// error rather than silently doing nothing.
+    /// Enter a file-watch loop that recompiles each `.ruitl` file under
+    /// `src_dir` as it changes, and rewrites `mod.rs` when one is added,
+    /// removed or renamed. Compile errors are logged and watching continues.
+    /// Gated on the `dev` feature (`hotwatch` is an optional dependency).
+    /// When the feature is off, returns a clear error rather than silently
+    /// doing nothing.
     #[cfg(feature = "dev")]
     fn run_watch_loop<F>(&self, src_dir: &Path, _compile_once: &F) -> Result<()>
     where
@@ -455,27 +798,34 @@ impl CliApp {
 
         let src_owned = src_dir.to_path_buf();
         let log = self.clone_logger();
+        let options = self.compile_options.clone();
         hotwatch
             .watch(src_dir, move |event: Event| {
-                // notify 4's DebouncedEvent is a path-bearing enum. Match the
-                // variants that indicate real content changes, and skip the
-                // `Notice*` variants (fired before the filesystem settles) +
-                // `Chmod` (permission-only).
-                let changed: Option<&PathBuf> = match &event {
-                    Event::Create(p)
-                    | Event::Write(p)
-                    | Event::Remove(p)
-                    | Event::Rename(p, _) => Some(p),
-                    _ => None,
+                // notify 4's DebouncedEvent is a path-bearing enum; the
+                // 150ms delay above already folds an editor's burst of
+                // writes into one event. Match the variants that indicate
+                // real content changes, and skip the `Notice*` variants
+                // (fired before the filesystem settles) + `Chmod`
+                // (permission-only).
+                let (removed, changed): (Option<&PathBuf>, Option<&PathBuf>) = match &event {
+                    Event::Write(p) | Event::Create(p) => (None, Some(p)),
+                    Event::Remove(p) => (Some(p), None),
+                    Event::Rename(from, to) => (Some(from), Some(to)),
+                    _ => return,
                 };
-                let Some(path) = changed else { return };
-                if path.extension().map(|e| e != "ruitl").unwrap_or(true) {
+                let is_ruitl = |p: &&PathBuf| p.extension().map(|e| e == "ruitl").unwrap_or(false);
+                let removed = removed.filter(is_ruitl);
+                let changed = changed.filter(is_ruitl);
+                if removed.is_none() && changed.is_none() {
                     return;
                 }
-                log.info(&format!("Change detected in {} — recompiling...", path.display()));
-                match ruitl_compiler::compile_dir_sibling(&src_owned) {
-                    Ok(out) => log.success(&format!("✓ Recompiled {} templates", out.len())),
-                    Err(e) => log.warning(&format!("Recompile failed: {}", e)),
+                // Only a new, deleted or renamed template changes which
+                // modules `mod.rs` declares.
+                let layout_changed = !matches!(event, Event::Write(_));
+                if let Err(e) =
+                    recompile_changed(&src_owned, removed, changed, layout_changed, &options, &log)
+                {
+                    log.warning(&format!("Recompile failed: {}", e));
                 }
             })
             .map_err(|e| RuitlError::generic(format!("Failed to watch '{}': {}", src_dir.display(), e)))?;
@@ -496,53 +846,73 @@ impl CliApp {
         ))
     }
 
+    #[cfg(feature = "dev")]
     fn clone_logger(&self) -> WatchLogger {
         WatchLogger {
             verbose: self.verbose,
         }
     }
 
-    /// Generate a scaffold project structure
+    /// Generate a scaffold project structure. With `dry_run`, nothing is
+    /// written; the paths that would be created are printed instead.
     async fn scaffold_project(
         &self,
         name: &str,
         target: &Path,
         with_server: bool,
         with_examples: bool,
+        dry_run: bool,
     ) -> Result<()> {
-        self.log_info(&format!("Creating new RUITL project: {}", name));
-
         let project_dir = target.join(name);
+        let sink = ScaffoldSink::new(dry_run);
+        if dry_run {
+            self.log_info(&format!("Planning RUITL project (dry run): {}", name));
+        } else {
+            self.log_info(&format!("Creating new RUITL project: {}", name));
+        }
 
         // Create project directory structure
-        self.create_project_structure(&project_dir, with_server, with_examples)?;
+        self.create_project_structure(&sink, &project_dir, with_server, with_examples)?;
 
         // Generate configuration files
-        self.generate_config_files(&project_dir, name)?;
+        self.generate_config_files(&sink, &project_dir, name)?;
 
         // Generate example templates if requested
         if with_examples {
-            self.generate_example_templates(&project_dir)?;
+            self.generate_example_templates(&sink, &project_dir)?;
         }
 
         // Generate server implementation if requested
         if with_server {
-            self.generate_server_implementation(&project_dir)?;
+            self.generate_server_implementation(&sink, &project_dir)?;
         }
 
         // Generate build files
-        self.generate_build_files(&project_dir, name, with_server)?;
+        self.generate_build_files(&sink, &project_dir, name, with_server)?;
 
         // Generate RUITL binary wrapper
-        self.generate_ruitl_binary_wrapper(&project_dir)?;
+        self.generate_ruitl_binary_wrapper(&sink, &project_dir)?;
 
-        // Compile example templates if they were generated
+        // Compile example templates if they were generated. A dry run can't
+        // compile, so it lists the files the compile would write instead.
         if with_examples {
-            self.compile_initial_templates(&project_dir).await?;
+            if dry_run {
+                sink.plan_compiled_templates(&project_dir.join("templates"));
+            } else {
+                self.compile_initial_templates(&project_dir).await?;
+            }
         }
 
         // Generate static assets
-        self.generate_static_assets(&project_dir)?;
+        self.generate_static_assets(&sink, &project_dir)?;
+
+        if dry_run {
+            println!("Would create in {}:", project_dir.display());
+            for line in sink.plan_lines(&project_dir) {
+                println!("  {}", line);
+            }
+            return Ok(());
+        }
 
         self.log_success(&format!(
             "✓ Created RUITL project: {}",
@@ -556,6 +926,7 @@ impl CliApp {
     /// Create the basic project directory structure
     fn create_project_structure(
         &self,
+        sink: &ScaffoldSink,
         project_dir: &Path,
         with_server: bool,
         with_examples: bool,
@@ -570,7 +941,7 @@ impl CliApp {
 
         for dir in dirs {
             let path = project_dir.join(dir);
-            fs::create_dir_all(&path).map_err(|e| {
+            sink.create_dir(&path).map_err(|e| {
                 RuitlError::config(format!(
                     "Failed to create directory '{}': {}",
                     path.display(),
@@ -580,17 +951,18 @@ impl CliApp {
         }
 
         if with_server {
-            fs::create_dir_all(project_dir.join("src").join("handlers")).map_err(|e| {
-                RuitlError::config(format!("Failed to create handlers directory: {}", e))
-            })?;
+            sink.create_dir(project_dir.join("src").join("handlers"))
+                .map_err(|e| {
+                    RuitlError::config(format!("Failed to create handlers directory: {}", e))
+                })?;
         }
 
         // Create bin directory for RUITL binary
-        fs::create_dir_all(project_dir.join("bin"))
+        sink.create_dir(project_dir.join("bin"))
             .map_err(|e| RuitlError::config(format!("Failed to create bin directory: {}", e)))?;
 
         if with_examples {
-            fs::create_dir_all(project_dir.join("examples")).map_err(|e| {
+            sink.create_dir(project_dir.join("examples")).map_err(|e| {
                 RuitlError::config(format!("Failed to create examples directory: {}", e))
             })?;
         }
@@ -599,7 +971,12 @@ impl CliApp {
     }
 
     /// Generate configuration files
-    fn generate_config_files(&self, project_dir: &Path, name: &str) -> Result<()> {
+    fn generate_config_files(
+        &self,
+        sink: &ScaffoldSink,
+        project_dir: &Path,
+        name: &str,
+    ) -> Result<()> {
         // Generate ruitl.toml
         let ruitl_config = format!(
             r#"[project]
@@ -610,12 +987,18 @@ authors = ["Your Name <your.email@example.com>"]
 
 [build]
 template_dir = "templates"
+out_dir = "generated"
 src_dir = "src"
+
+# Run with `ruitl run <name>`
+[scripts]
+compile = "ruitl compile"
+watch = "ruitl compile --watch"
 "#,
             name
         );
 
-        fs::write(project_dir.join("ruitl.toml"), ruitl_config)
+        sink.write(project_dir.join("ruitl.toml"), ruitl_config)
             .map_err(|e| RuitlError::config(format!("Failed to write ruitl.toml: {}", e)))?;
 
         // Generate .gitignore
@@ -637,7 +1020,7 @@ Thumbs.db
 *.log
 "#;
 
-        fs::write(project_dir.join(".gitignore"), gitignore)
+        sink.write(project_dir.join(".gitignore"), gitignore)
             .map_err(|e| RuitlError::config(format!("Failed to write .gitignore: {}", e)))?;
 
         // Generate README.md
@@ -772,14 +1155,14 @@ Unlike typical web frameworks, this project demonstrates:
             name, name
         );
 
-        fs::write(project_dir.join("README.md"), readme)
+        sink.write(project_dir.join("README.md"), readme)
             .map_err(|e| RuitlError::config(format!("Failed to write README.md: {}", e)))?;
 
         Ok(())
     }
 
     /// Generate example templates
-    fn generate_example_templates(&self, project_dir: &Path) -> Result<()> {
+    fn generate_example_templates(&self, sink: &ScaffoldSink, project_dir: &Path) -> Result<()> {
         // Generate Button.ruitl
         let button_template = r#"// RUITL Button Component
 // Example demonstrating basic component structure with props and conditionals
@@ -806,7 +1189,7 @@ ruitl Button(props: ButtonProps) {
 }
 "#;
 
-        fs::write(project_dir.join("templates/Button.ruitl"), button_template)
+        sink.write(project_dir.join("templates/Button.ruitl"), button_template)
             .map_err(|e| RuitlError::config(format!("Failed to write Button.ruitl: {}", e)))?;
 
         // Generate Card.ruitl
@@ -841,7 +1224,7 @@ ruitl Card(props: CardProps) {
 }
 "#;
 
-        fs::write(project_dir.join("templates/Card.ruitl"), card_template)
+        sink.write(project_dir.join("templates/Card.ruitl"), card_template)
             .map_err(|e| RuitlError::config(format!("Failed to write Card.ruitl: {}", e)))?;
 
         // Generate Layout.ruitl
@@ -871,7 +1254,7 @@ ruitl Layout(props: LayoutProps) {
 }
 "#;
 
-        fs::write(project_dir.join("templates/Layout.ruitl"), layout_template)
+        sink.write(project_dir.join("templates/Layout.ruitl"), layout_template)
             .map_err(|e| RuitlError::config(format!("Failed to write Layout.ruitl: {}", e)))?;
 
         // Generate Page.ruitl
@@ -912,24 +1295,28 @@ ruitl Page(props: PageProps) {
 }
 "#;
 
-        fs::write(project_dir.join("templates/Page.ruitl"), page_template)
+        sink.write(project_dir.join("templates/Page.ruitl"), page_template)
             .map_err(|e| RuitlError::config(format!("Failed to write Page.ruitl: {}", e)))?;
 
         Ok(())
     }
 
     /// Generate server implementation
-    fn generate_server_implementation(&self, project_dir: &Path) -> Result<()> {
+    fn generate_server_implementation(
+        &self,
+        sink: &ScaffoldSink,
+        project_dir: &Path,
+    ) -> Result<()> {
         // Generate main.rs with server
         let main_rs = self.generate_main_rs_content();
 
-        fs::write(project_dir.join("src/main.rs"), main_rs)
+        sink.write(project_dir.join("src/main.rs"), main_rs)
             .map_err(|e| RuitlError::config(format!("Failed to write main.rs: {}", e)))?;
 
         // Generate handlers/mod.rs
         let handlers_mod = self.generate_handlers_mod_content();
 
-        fs::write(
+        sink.write(
             project_dir
                 .join("src")
                 .join("handlers")
@@ -945,6 +1332,7 @@ ruitl Page(props: PageProps) {
     /// Generate build files
     fn generate_build_files(
         &self,
+        sink: &ScaffoldSink,
         project_dir: &Path,
         name: &str,
         with_server: bool,
@@ -1020,7 +1408,7 @@ watch = "cargo run --bin ruitl -- compile --watch"
             )
         };
 
-        fs::write(project_dir.join("Cargo").with_extension("toml"), cargo_toml)
+        sink.write(project_dir.join("Cargo").with_extension("toml"), cargo_toml)
             .map_err(|e| RuitlError::config(format!("Failed to write Cargo.toml: {}", e)))?;
 
         // Generate lib.rs if no server, or basic lib.rs if server. In both
@@ -1054,7 +1442,7 @@ pub fn main() {
             project_dir.join("src").join("main").with_extension("rs")
         };
 
-        fs::write(&lib_path, lib_rs).map_err(|e| {
+        sink.write(&lib_path, lib_rs).map_err(|e| {
             RuitlError::config(format!("Failed to write {}: {}", lib_path.display(), e))
         })?;
 
@@ -1081,7 +1469,7 @@ pub fn main() {
     }
 
     /// Generate RUITL binary wrapper
-    fn generate_ruitl_binary_wrapper(&self, project_dir: &Path) -> Result<()> {
+    fn generate_ruitl_binary_wrapper(&self, sink: &ScaffoldSink, project_dir: &Path) -> Result<()> {
         let binary_wrapper = r#"//! RUITL CLI Binary Wrapper
 //! This file provides a local RUITL CLI for template compilation
 
@@ -1096,18 +1484,18 @@ async fn main() {
 }
 "#;
 
-        fs::write(project_dir.join("bin").join("ruitl.rs"), binary_wrapper)
+        sink.write(project_dir.join("bin").join("ruitl.rs"), binary_wrapper)
             .map_err(|e| RuitlError::config(format!("Failed to write bin/ruitl.rs: {}", e)))?;
 
         Ok(())
     }
 
     /// Generate static assets (CSS and JS)
-    fn generate_static_assets(&self, project_dir: &Path) -> Result<()> {
+    fn generate_static_assets(&self, sink: &ScaffoldSink, project_dir: &Path) -> Result<()> {
         // Generate CSS
         let css = self.generate_css_content();
 
-        fs::write(
+        sink.write(
             project_dir
                 .join("static")
                 .join("css")
@@ -1120,7 +1508,7 @@ async fn main() {
         // Generate JavaScript
         let js = self.generate_js_content();
 
-        fs::write(
+        sink.write(
             project_dir
                 .join("static")
                 .join("js")
@@ -1216,6 +1604,8 @@ async fn main() {
     }
 
     /// Generate main.rs content for server
+    // Kept as `format!` so the `{{`/`}}` escaping matches sibling generators.
+    #[allow(clippy::useless_format)]
     fn generate_main_rs_content(&self) -> String {
         format!(
             r#"//! Main application entry point with HTTP server
@@ -1263,7 +1653,13 @@ async fn handle_request(req: Request<Body>) -> Result<Response<Body>, Infallible
     let response = match (req.method(), req.uri().path()) {{
         (&Method::GET, "/") => serve_home().await,
         (&Method::GET, "/about") => serve_about().await,
-        (&Method::GET, path) if path.starts_with("/static/") => serve_static(path).await,
+        (&Method::GET, path) if path.starts_with("/static/") => {{
+            let accept_encoding = req
+                .headers()
+                .get("accept-encoding")
+                .and_then(|value| value.to_str().ok());
+            serve_static(path, accept_encoding).await
+        }}
         _ => serve_404().await,
     }};
 
@@ -1274,12 +1670,15 @@ async fn handle_request(req: Request<Body>) -> Result<Response<Body>, Infallible
     }
 
     /// Generate handlers/mod.rs content
+    // Kept as `format!` so the `{{`/`}}` escaping matches sibling generators.
+    #[allow(clippy::useless_format)]
     fn generate_handlers_mod_content(&self) -> String {
         format!(
             r##"//! HTTP request handlers
 
 use hyper::{{Body, Response, StatusCode}};
 use std::fs;
+use std::path::Path;
 use ruitl::{{Component, ComponentContext}};
 
 // Import generated components from sibling *_ruitl.rs files
@@ -1297,8 +1696,8 @@ pub async fn serve_home() -> Response<Body> {{
         variant: "default".to_string(),
     }};
 
-    let card_html = match card.render(&card_props, &context) {{
-        Ok(html) => html.render(),
+    let card_html = match card.render_to_string(&card_props, &context) {{
+        Ok(html) => html,
         Err(e) => return error_response(&format!("Card render error: {{}}", e)),
     }};
 
@@ -1312,8 +1711,8 @@ pub async fn serve_home() -> Response<Body> {{
         onclick: Some("window.location.href='/about'".to_string()),
     }};
 
-    let button_html = match button.render(&button_props, &context) {{
-        Ok(html) => html.render(),
+    let button_html = match button.render_to_string(&button_props, &context) {{
+        Ok(html) => html,
         Err(e) => return error_response(&format!("Button render error: {{}}", e)),
     }};
 
@@ -1382,8 +1781,8 @@ pub async fn serve_about() -> Response<Body> {{
         variant: "default".to_string(),
     }};
 
-    let card_html = match card.render(&card_props, &context) {{
-        Ok(html) => html.render(),
+    let card_html = match card.render_to_string(&card_props, &context) {{
+        Ok(html) => html,
         Err(e) => return error_response(&format!("Card render error: {{}}", e)),
     }};
 
@@ -1397,8 +1796,8 @@ pub async fn serve_about() -> Response<Body> {{
         onclick: Some("window.location.href='/'".to_string()),
     }};
 
-    let button_html = match button.render(&button_props, &context) {{
-        Ok(html) => html.render(),
+    let button_html = match button.render_to_string(&button_props, &context) {{
+        Ok(html) => html,
         Err(e) => return error_response(&format!("Button render error: {{}}", e)),
     }};
 
@@ -1457,11 +1856,14 @@ pub async fn serve_about() -> Response<Body> {{
         .unwrap()
 }}
 
-pub async fn serve_static(path: &str) -> Response<Body> {{
+/// Serve a file from `static/`, preferring a precompressed `.br` / `.gz`
+/// sibling when the client accepts it.
+pub async fn serve_static(path: &str, accept_encoding: Option<&str>) -> Response<Body> {{
     let file_path = path.strip_prefix("/static/").unwrap_or(path);
     let full_path = format!("static/{{}}", file_path);
+    let asset = ruitl::assets::resolve_precompressed(Path::new(&full_path), accept_encoding);
 
-    match fs::read(&full_path) {{
+    match fs::read(&asset.path) {{
         Ok(contents) => {{
             let content_type = match full_path.split('.').last() {{
                 Some("css") => "text/css",
@@ -1473,10 +1875,13 @@ pub async fn serve_static(path: &str) -> Response<Body> {{
                 _ => "application/octet-stream",
             }};
 
-            Response::builder()
+            let mut response = Response::builder()
                 .header("content-type", content_type)
-                .body(Body::from(contents))
-                .unwrap()
+                .header("vary", "accept-encoding");
+            if let Some(encoding) = asset.content_encoding {{
+                response = response.header("content-encoding", encoding);
+            }}
+            response.body(Body::from(contents)).unwrap()
         }}
         Err(_) => serve_404().await,
     }}
@@ -4,11 +4,15 @@
 //! reusable UI components with props, state, and lifecycle methods.
 
 use crate::error::{Result, RuitlError};
-use crate::html::Html;
-use serde::{Deserialize, Serialize};
+use crate::html::{div, Html};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::any::Any;
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 /// Trait for component properties
 pub trait ComponentProps: Debug + Clone + Send + Sync + 'static {
@@ -31,6 +35,24 @@ pub trait ComponentProps: Debug + Clone + Send + Sync + 'static {
             "from_map not implemented for this component",
         ))
     }
+
+    /// Overlay the fields present in `other_map` onto these props, leaving
+    /// the rest untouched. Works through a serde round-trip, so values must
+    /// deserialize into the field types.
+    fn merge(&mut self, other_map: &HashMap<String, serde_json::Value>) -> Result<()>
+    where
+        Self: Serialize + DeserializeOwned,
+    {
+        let mut value = serde_json::to_value(&*self)?;
+        let fields = value.as_object_mut().ok_or_else(|| {
+            RuitlError::component("merge requires props that serialize to a JSON object")
+        })?;
+        for (key, field) in other_map {
+            fields.insert(key.clone(), field.clone());
+        }
+        *self = serde_json::from_value(value)?;
+        Ok(())
+    }
 }
 
 /// Empty props for components that don't need properties
@@ -39,6 +61,124 @@ pub struct EmptyProps;
 
 impl ComponentProps for EmptyProps {}
 
+/// Per-request security values a page needs to emit: the CSP nonce for
+/// inline `<script>`/`<style>` tags and the CSRF token for forms
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SecurityContext {
+    /// Nonce matching the response's `Content-Security-Policy` header
+    pub nonce: Option<String>,
+    /// Token to embed in forms and check on submission
+    pub csrf_token: Option<String>,
+}
+
+impl SecurityContext {
+    /// Create an empty security context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the CSP nonce
+    pub fn with_nonce<S: Into<String>>(mut self, nonce: S) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Set the CSRF token
+    pub fn with_csrf_token<S: Into<String>>(mut self, token: S) -> Self {
+        self.csrf_token = Some(token.into());
+        self
+    }
+
+    /// The CSP nonce, if one was issued for this request
+    pub fn nonce(&self) -> Option<&str> {
+        self.nonce.as_deref()
+    }
+
+    /// The CSRF token, if one was issued for this request
+    pub fn csrf_token(&self) -> Option<&str> {
+        self.csrf_token.as_deref()
+    }
+}
+
+/// Shared flag for abandoning a render, e.g. when the client disconnects or
+/// a timeout fires. Clones share the flag, so cancel from any task holding
+/// one and the render sees it at its next component boundary.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Create a token that has not been cancelled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every render holding this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether [`cancel`](Self::cancel) has been called
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Traffic split for one A/B experiment, read in templates with
+/// [`ComponentContext::variant`]. Assignment hashes the experiment name and
+/// client id, so a client keeps its variant across requests and restarts
+/// without any stored state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Experiment {
+    variants: Vec<(String, u32)>,
+}
+
+impl Experiment {
+    /// Two variants, `"a"` and `"b"`, with `percent_b` percent of clients
+    /// (clamped to 100) getting `"b"`
+    pub fn ab(percent_b: u32) -> Self {
+        let percent_b = percent_b.min(100);
+        Self::weighted([("a", 100 - percent_b), ("b", percent_b)])
+    }
+
+    /// Variants with relative weights, e.g. `[("control", 80), ("new", 20)]`.
+    /// Zero-weight variants are never picked.
+    pub fn weighted<I, S>(variants: I) -> Self
+    where
+        I: IntoIterator<Item = (S, u32)>,
+        S: Into<String>,
+    {
+        Self {
+            variants: variants
+                .into_iter()
+                .map(|(name, weight)| (name.into(), weight))
+                .collect(),
+        }
+    }
+
+    /// The variant `client_id` falls in for experiment `name`, or `None`
+    /// when every weight is zero
+    pub fn assign(&self, name: &str, client_id: &str) -> Option<&str> {
+        let total: u64 = self.variants.iter().map(|(_, w)| u64::from(*w)).sum();
+        if total == 0 {
+            return None;
+        }
+        let digest = md5::compute(format!("{}:{}", name, client_id));
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        let mut bucket = u64::from_be_bytes(bytes) % total;
+        for (variant, weight) in &self.variants {
+            let weight = u64::from(*weight);
+            if bucket < weight {
+                return Some(variant);
+            }
+            bucket -= weight;
+        }
+        None
+    }
+}
+
 /// Context passed to components during rendering
 #[derive(Debug)]
 pub struct ComponentContext {
@@ -52,18 +192,30 @@ pub struct ComponentContext {
     pub env: HashMap<String, String>,
     /// Custom data
     pub data: HashMap<String, Box<dyn Any + Send + Sync>>,
-}
-
-impl Clone for ComponentContext {
-    fn clone(&self) -> Self {
-        Self {
-            path: self.path.clone(),
-            query: self.query.clone(),
-            headers: self.headers.clone(),
-            env: self.env.clone(),
-            data: HashMap::new(), // Cannot clone Box<dyn Any>, so start with empty
-        }
-    }
+    /// When the request started. Captured once at creation so every
+    /// component in a render sees the same instant.
+    pub request_time: SystemTime,
+    /// Wrap each `@Component` invocation's output in
+    /// `<!-- ruitl:component:Name -->` comments (debug builds only)
+    pub component_boundaries: bool,
+    /// Feature flags for gradual rollouts; read with [`ComponentContext::feature`]
+    pub feature_flags: HashMap<String, bool>,
+    /// A/B experiments by name; read with [`ComponentContext::variant`]
+    pub experiments: HashMap<String, Experiment>,
+    /// Stable id for the visitor (session cookie, user id) that experiment
+    /// variants are assigned by
+    pub client_id: Option<String>,
+    /// CSP nonce and CSRF token for this request
+    pub security: SecurityContext,
+    /// Replace failing `@Component` invocations with an error placeholder
+    /// instead of aborting the render (debug builds only); see
+    /// [`ComponentContext::render_component`]
+    pub collect_errors: bool,
+    /// Errors swallowed while `collect_errors` was on
+    errors: Mutex<Vec<RuitlError>>,
+    /// Checked at every component boundary; see
+    /// [`ComponentContext::check_cancelled`]
+    pub cancellation: CancellationToken,
 }
 
 impl Default for ComponentContext {
@@ -74,6 +226,36 @@ impl Default for ComponentContext {
             headers: HashMap::new(),
             env: HashMap::new(),
             data: HashMap::new(),
+            request_time: SystemTime::now(),
+            component_boundaries: false,
+            feature_flags: HashMap::new(),
+            experiments: HashMap::new(),
+            client_id: None,
+            security: SecurityContext::default(),
+            collect_errors: false,
+            errors: Mutex::new(Vec::new()),
+            cancellation: CancellationToken::default(),
+        }
+    }
+}
+
+impl Clone for ComponentContext {
+    fn clone(&self) -> Self {
+        Self {
+            path: self.path.clone(),
+            query: self.query.clone(),
+            headers: self.headers.clone(),
+            env: self.env.clone(),
+            data: HashMap::new(), // Cannot clone Box<dyn Any>, so start with empty
+            request_time: self.request_time,
+            component_boundaries: self.component_boundaries,
+            feature_flags: self.feature_flags.clone(),
+            experiments: self.experiments.clone(),
+            client_id: self.client_id.clone(),
+            security: self.security.clone(),
+            collect_errors: self.collect_errors,
+            errors: Mutex::new(Vec::new()), // Collected errors belong to one render
+            cancellation: self.cancellation.clone(),
         }
     }
 }
@@ -108,6 +290,153 @@ impl ComponentContext {
         self
     }
 
+    /// Override the request timestamp (e.g. with the time the server
+    /// accepted the connection, or a fixed instant in tests)
+    pub fn with_request_time(mut self, time: SystemTime) -> Self {
+        self.request_time = time;
+        self
+    }
+
+    /// The request timestamp. Use this instead of `SystemTime::now()` in
+    /// components so "last updated" text and cache headers agree.
+    pub fn request_time(&self) -> SystemTime {
+        self.request_time
+    }
+
+    /// Set a feature flag
+    pub fn with_feature<K: Into<String>>(mut self, name: K, enabled: bool) -> Self {
+        self.feature_flags.insert(name.into(), enabled);
+        self
+    }
+
+    /// Whether feature `name` is on. Flags that were never set are off, so
+    /// templates can gate markup with `if context.feature("new_nav") { … }`.
+    pub fn feature(&self, name: &str) -> bool {
+        self.feature_flags.get(name).copied().unwrap_or(false)
+    }
+
+    /// Register an A/B experiment
+    pub fn with_experiment<K: Into<String>>(mut self, name: K, experiment: Experiment) -> Self {
+        self.experiments.insert(name.into(), experiment);
+        self
+    }
+
+    /// Set the id experiment variants are assigned by, e.g. the value of a
+    /// session cookie
+    pub fn with_client_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.client_id = Some(id.into());
+        self
+    }
+
+    /// This client's variant of experiment `name`, for
+    /// `if context.variant("hero") == Some("b") { … }`. `None` when the
+    /// experiment isn't registered or no client id is set; treat that as the
+    /// control.
+    pub fn variant(&self, name: &str) -> Option<&str> {
+        let client_id = self.client_id.as_deref()?;
+        self.experiments.get(name)?.assign(name, client_id)
+    }
+
+    /// Set the request's security values
+    pub fn with_security(mut self, security: SecurityContext) -> Self {
+        self.security = security;
+        self
+    }
+
+    /// The request's security values
+    pub fn security(&self) -> &SecurityContext {
+        &self.security
+    }
+
+    /// Shorthand for `self.security().nonce()`
+    pub fn nonce(&self) -> Option<&str> {
+        self.security.nonce()
+    }
+
+    /// Shorthand for `self.security().csrf_token()`
+    pub fn csrf_token(&self) -> Option<&str> {
+        self.security.csrf_token()
+    }
+
+    /// Turn component boundary comments on or off; see
+    /// [`component_boundary`](Self::component_boundary)
+    pub fn with_component_boundaries(mut self, enabled: bool) -> Self {
+        self.component_boundaries = enabled;
+        self
+    }
+
+    /// Called by generated code around every `@Component(...)` invocation.
+    /// With `component_boundaries` set in a debug build, brackets `html`
+    /// with `<!-- ruitl:component:Name -->` / `<!-- /ruitl:component:Name -->`
+    /// so the markup can be traced back to its component; otherwise returns
+    /// it unchanged.
+    pub fn component_boundary(&self, name: &str, html: Html) -> Html {
+        if !(cfg!(debug_assertions) && self.component_boundaries) {
+            return html;
+        }
+        Html::Fragment(vec![
+            Html::raw(format!("<!-- ruitl:component:{} -->", name)),
+            html,
+            Html::raw(format!("<!-- /ruitl:component:{} -->", name)),
+        ])
+    }
+
+    /// Turn error collection on or off; see
+    /// [`render_component`](Self::render_component)
+    pub fn with_error_collection(mut self, enabled: bool) -> Self {
+        self.collect_errors = enabled;
+        self
+    }
+
+    /// Render under `token`, so cancelling it stops the render at the next
+    /// component boundary
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Fail with a [`RuitlError::Cancelled`] error once the render's
+    /// cancellation token has been triggered. Generated code and
+    /// [`ComponentRenderer`] call this between components; long-running
+    /// or async components can call it too.
+    pub fn check_cancelled(&self) -> Result<()> {
+        if self.cancellation.is_cancelled() {
+            return Err(RuitlError::cancelled(format!(
+                "render of {} was cancelled",
+                self.path.as_deref().unwrap_or("page")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Called by generated code with the result of every `@Component(...)`
+    /// invocation. Fails if the render was cancelled. Other errors propagate
+    /// unless `collect_errors` is set in a debug build, in which case the
+    /// error is recorded and rendered as a visible `<div class="ruitl-error">`
+    /// placeholder so the rest of the page still renders.
+    pub fn render_component(&self, name: &str, result: Result<Html>) -> Result<Html> {
+        self.check_cancelled()?;
+        match result {
+            Err(err) if cfg!(debug_assertions) && self.collect_errors => {
+                let placeholder = div()
+                    .class("ruitl-error")
+                    .attr("data-component", name)
+                    .text(format!("{}: {}", name, err));
+                self.errors
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(err);
+                Ok(placeholder.into())
+            }
+            result => result,
+        }
+    }
+
+    /// Drain the errors recorded by [`render_component`](Self::render_component)
+    pub fn take_errors(&self) -> Vec<RuitlError> {
+        std::mem::take(&mut *self.errors.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
     /// Add custom data
     pub fn with_data<K: Into<String>, V: Any + Send + Sync>(mut self, key: K, value: V) -> Self {
         self.data.insert(key.into(), Box::new(value));
@@ -119,11 +448,52 @@ impl ComponentContext {
         self.query.get(key)
     }
 
+    /// Get a query parameter parsed as `T`.
+    ///
+    /// Errors if the parameter is missing or does not parse, so handlers can
+    /// use `?` instead of unwrapping `get_query` and `parse` by hand.
+    pub fn query_as<T>(&self, key: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let raw = self
+            .get_query(key)
+            .ok_or_else(|| RuitlError::validation(format!("Missing query parameter '{}'", key)))?;
+        raw.parse().map_err(|e| {
+            RuitlError::validation(format!(
+                "Invalid query parameter '{}' = '{}': {}",
+                key, raw, e
+            ))
+        })
+    }
+
     /// Get header
     pub fn get_header(&self, key: &str) -> Option<&String> {
         self.headers.get(key)
     }
 
+    /// Whether the request's `If-None-Match` header matches `etag`, i.e. the
+    /// client already holds this page and a `304 Not Modified` can be sent
+    /// instead of the body. Handles `*`, comma-separated lists and weak
+    /// (`W/`) validators; the header name is matched case-insensitively.
+    pub fn is_not_modified(&self, etag: &str) -> bool {
+        let Some(header) = self
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("if-none-match"))
+            .map(|(_, v)| v)
+        else {
+            return false;
+        };
+
+        let wanted = etag.trim_start_matches("W/");
+        header
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == wanted)
+    }
+
     /// Get environment variable
     pub fn get_env(&self, key: &str) -> Option<&String> {
         self.env.get(key)
@@ -143,6 +513,12 @@ pub trait Component: Debug + Send + Sync + 'static {
     /// Render the component to HTML
     fn render(&self, props: &Self::Props, context: &ComponentContext) -> Result<Html>;
 
+    /// Render and stringify in one step — shorthand for
+    /// `self.render(props, context)?.render()`.
+    fn render_to_string(&self, props: &Self::Props, context: &ComponentContext) -> Result<String> {
+        Ok(self.render(props, context)?.render())
+    }
+
     /// Get the component name (used for debugging and error messages)
     fn name(&self) -> &'static str {
         std::any::type_name::<Self>()
@@ -313,6 +689,13 @@ impl ComponentRegistry {
     pub fn list_components(&self) -> Vec<String> {
         self.components.keys().cloned().collect()
     }
+
+    /// Registered component names in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.components.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
 }
 
 /// Helper struct for component rendering
@@ -356,10 +739,12 @@ impl ComponentRenderer {
             .get::<C>(name)
             .ok_or_else(|| RuitlError::component(format!("Component '{}' not found", name)))?;
 
+        context.check_cancelled()?;
         component.validate_props(props)?;
         component.before_render(props, context)?;
         let html = component.render(props, context)?;
         component.after_render(props, context)?;
+        context.check_cancelled()?;
 
         Ok(html)
     }
@@ -419,7 +804,7 @@ macro_rules! impl_static_component {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::html::{div, Html};
+    use crate::html::{div, Html, HtmlElement};
 
     #[derive(Debug, Clone)]
     struct TestProps {
@@ -456,6 +841,35 @@ mod tests {
         assert_eq!(html.render(), "<div>Hello, World!</div>");
     }
 
+    #[test]
+    fn test_props_merge_overlays_provided_fields() {
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        struct CardProps {
+            title: String,
+            count: u32,
+            featured: bool,
+        }
+        impl ComponentProps for CardProps {}
+
+        let mut props = CardProps {
+            title: "Base".to_string(),
+            count: 1,
+            featured: false,
+        };
+        let overrides = HashMap::from([
+            ("count".to_string(), serde_json::json!(5)),
+            ("featured".to_string(), serde_json::json!(true)),
+        ]);
+        props.merge(&overrides).unwrap();
+        assert_eq!(props.title, "Base");
+        assert_eq!(props.count, 5);
+        assert!(props.featured);
+
+        let bad = HashMap::from([("count".to_string(), serde_json::json!("many"))]);
+        assert!(props.merge(&bad).is_err());
+        assert_eq!(props.count, 5);
+    }
+
     #[test]
     fn test_component_registry() {
         let mut registry = ComponentRegistry::new();
@@ -484,6 +898,20 @@ mod tests {
         assert_eq!(html.render(), "<div>Test message</div>");
     }
 
+    #[test]
+    fn test_render_to_string_matches_render() {
+        let props = TestProps {
+            message: "<b>hi</b>".to_string(),
+        };
+        let context = ComponentContext::new();
+
+        let rendered = TestComponent.render(&props, &context).unwrap().render();
+        assert_eq!(
+            TestComponent.render_to_string(&props, &context).unwrap(),
+            rendered
+        );
+    }
+
     #[test]
     fn test_component_context() {
         let context = ComponentContext::new()
@@ -501,6 +929,251 @@ mod tests {
         assert_eq!(context.get_env("NODE_ENV"), Some(&"production".to_string()));
     }
 
+    #[test]
+    fn test_query_as() {
+        let context = ComponentContext::new()
+            .with_query("page", "3")
+            .with_query("limit", "ten");
+
+        assert_eq!(context.query_as::<u64>("page").unwrap(), 3);
+
+        let missing = context.query_as::<u64>("offset").unwrap_err();
+        assert!(missing.is_validation());
+        assert!(missing
+            .to_string()
+            .contains("Missing query parameter 'offset'"));
+
+        let invalid = context.query_as::<u64>("limit").unwrap_err();
+        assert!(invalid.is_validation());
+        assert!(invalid.to_string().contains("'limit' = 'ten'"));
+    }
+
+    #[test]
+    fn test_is_not_modified() {
+        let etag = crate::html::etag("<p>cached</p>");
+
+        let matching = ComponentContext::new().with_header("If-None-Match", etag.clone());
+        assert!(matching.is_not_modified(&etag));
+
+        let listed =
+            ComponentContext::new().with_header("if-none-match", format!("\"stale\", W/{}", etag));
+        assert!(listed.is_not_modified(&etag));
+
+        let stale = ComponentContext::new().with_header("if-none-match", "\"stale\"");
+        assert!(!stale.is_not_modified(&etag));
+
+        assert!(!ComponentContext::new().is_not_modified(&etag));
+    }
+
+    #[test]
+    fn test_request_time_is_stable_within_a_render() {
+        #[derive(Debug)]
+        struct Stamp;
+
+        impl Component for Stamp {
+            type Props = EmptyProps;
+
+            fn render(&self, _props: &Self::Props, context: &ComponentContext) -> Result<Html> {
+                let first = context.request_time();
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                assert_eq!(first, context.request_time());
+                Ok(Html::text(format!("{:?}", first)))
+            }
+        }
+
+        let context = ComponentContext::new();
+        let first = Stamp.render(&EmptyProps, &context).unwrap();
+        let second = Stamp.render(&EmptyProps, &context.clone()).unwrap();
+        assert_eq!(first, second);
+
+        let fixed = SystemTime::UNIX_EPOCH;
+        assert_eq!(
+            ComponentContext::new()
+                .with_request_time(fixed)
+                .request_time(),
+            fixed
+        );
+    }
+
+    #[test]
+    fn test_feature_flags_default_to_off() {
+        let context = ComponentContext::new()
+            .with_feature("new_nav", true)
+            .with_feature("beta", false);
+        assert!(context.feature("new_nav"));
+        assert!(!context.feature("beta"));
+        assert!(!context.feature("unset"));
+        assert!(context.clone().feature("new_nav"));
+    }
+
+    #[test]
+    fn test_experiment_variants_are_stable_and_follow_the_split() {
+        let context = ComponentContext::new()
+            .with_experiment("hero", Experiment::ab(30))
+            .with_client_id("client-42");
+        let first = context.variant("hero").unwrap();
+        for _ in 0..10 {
+            assert_eq!(context.variant("hero"), Some(first));
+        }
+        assert_eq!(context.clone().variant("hero"), Some(first));
+        assert_eq!(context.variant("unknown"), None);
+        assert_eq!(
+            ComponentContext::new()
+                .with_experiment("hero", Experiment::ab(30))
+                .variant("hero"),
+            None
+        );
+
+        let experiment = Experiment::ab(30);
+        let b = (0..10_000)
+            .filter(|i| experiment.assign("hero", &format!("client-{}", i)) == Some("b"))
+            .count();
+        assert!((2_700..=3_300).contains(&b), "{} of 10000 got b", b);
+
+        let all_control = Experiment::weighted([("control", 1), ("new", 0)]);
+        assert!((0..100).all(|i| all_control.assign("x", &i.to_string()) == Some("control")));
+        assert_eq!(Experiment::weighted([("a", 0)]).assign("x", "y"), None);
+    }
+
+    #[test]
+    fn test_component_boundary_comments() {
+        let html = Html::Element(div().text("hi"));
+
+        let plain = ComponentContext::new().component_boundary("Card", html.clone());
+        assert_eq!(plain.render(), "<div>hi</div>");
+
+        let marked = ComponentContext::new()
+            .with_component_boundaries(true)
+            .component_boundary("Card", html);
+        assert_eq!(
+            marked.render(),
+            "<!-- ruitl:component:Card --><div>hi</div><!-- /ruitl:component:Card -->"
+        );
+    }
+
+    #[test]
+    fn test_security_context_reaches_components() {
+        #[derive(Debug)]
+        struct Checkout;
+
+        impl Component for Checkout {
+            type Props = EmptyProps;
+
+            fn render(&self, _props: &EmptyProps, context: &ComponentContext) -> Result<Html> {
+                let mut form = Html::Element(div());
+                if let Some(token) = context.csrf_token() {
+                    form = Html::Element(
+                        HtmlElement::self_closing("input")
+                            .attr("type", "hidden")
+                            .attr("name", "csrf_token")
+                            .attr("value", token),
+                    );
+                }
+                let mut script = HtmlElement::new("script");
+                if let Some(nonce) = context.security().nonce() {
+                    script = script.attr("nonce", nonce);
+                }
+                Ok(Html::Fragment(vec![form, Html::Element(script)]))
+            }
+        }
+
+        let context = ComponentContext::new().with_security(
+            SecurityContext::new()
+                .with_nonce("r4nd0m")
+                .with_csrf_token("t0k3n"),
+        );
+        let html = Checkout.render_to_string(&EmptyProps, &context).unwrap();
+        assert!(
+            html.contains(r#"name="csrf_token" value="t0k3n""#),
+            "{}",
+            html
+        );
+        assert!(
+            html.contains(r#"<script nonce="r4nd0m"></script>"#),
+            "{}",
+            html
+        );
+
+        // Security values survive a clone, unlike `data`.
+        assert_eq!(context.clone().nonce(), Some("r4nd0m"));
+        assert_eq!(ComponentContext::new().csrf_token(), None);
+    }
+
+    #[test]
+    fn test_error_collection() {
+        // Mirrors the generated code for a template containing `@Broken()`.
+        fn page(context: &ComponentContext) -> Result<Html> {
+            let child = context.render_component("Broken", Err(RuitlError::render("boom")))?;
+            Ok(Html::Element(div().child(child)))
+        }
+
+        let dev = ComponentContext::new().with_error_collection(true);
+        assert_eq!(
+            page(&dev).unwrap().render(),
+            "<div><div class=\"ruitl-error\" data-component=\"Broken\">\
+             Broken: Render error: boom</div></div>"
+        );
+        let errors = dev.take_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], RuitlError::Render { .. }));
+        assert!(dev.take_errors().is_empty());
+
+        let prod = ComponentContext::new();
+        assert!(matches!(page(&prod), Err(RuitlError::Render { .. })));
+        assert!(prod.take_errors().is_empty());
+    }
+
+    #[test]
+    fn test_cancellation_stops_render_at_next_component() {
+        let rendered = std::cell::Cell::new(0);
+        // Mirrors generated code for `@Item() @Item() @Item()`, where the
+        // client disconnects while the second item renders.
+        let item = |token: &CancellationToken| -> Result<Html> {
+            rendered.set(rendered.get() + 1);
+            if rendered.get() == 2 {
+                token.cancel();
+            }
+            Ok(Html::text("item"))
+        };
+        let page = |context: &ComponentContext, token: &CancellationToken| -> Result<Html> {
+            let mut children = Vec::new();
+            for _ in 0..3 {
+                children.push(context.render_component("Item", item(token))?);
+            }
+            Ok(Html::fragment(children))
+        };
+
+        let token = CancellationToken::new();
+        let context = ComponentContext::new()
+            .with_path("/slow")
+            .with_cancellation(token.clone());
+        let err = page(&context, &token).unwrap_err();
+        assert!(err.is_cancelled());
+        assert_eq!(
+            err.to_string(),
+            "Render cancelled: render of /slow was cancelled"
+        );
+        assert_eq!(rendered.get(), 2);
+
+        // Error collection doesn't swallow a cancellation.
+        let dev = context.clone().with_error_collection(true);
+        assert!(dev.render_component("Item", Ok(Html::Empty)).is_err());
+        assert!(dev.take_errors().is_empty());
+
+        let mut renderer = ComponentRenderer::new();
+        renderer.register("test", TestComponent);
+        let props = TestProps {
+            message: "Hi".to_string(),
+        };
+        let err = renderer
+            .render::<TestComponent>("test", &props, &context)
+            .unwrap_err();
+        assert!(err.is_cancelled());
+        assert!(renderer
+            .render::<TestComponent>("test", &props, &ComponentContext::new())
+            .is_ok());
+    }
+
     #[test]
     fn test_empty_props() {
         let props = EmptyProps;
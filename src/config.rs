@@ -4,6 +4,7 @@
 
 use crate::error::{Result, RuitlError};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -18,6 +19,16 @@ pub struct RuitlConfig {
     /// a URL path to a component name plus a props JSON file.
     #[serde(default, rename = "routes")]
     pub routes: Vec<RouteConfig>,
+    /// Named shell commands from the `[scripts]` table, run with
+    /// `ruitl run <name>`. Kept as written: `${VAR}` in them is left for
+    /// the shell.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scripts: HashMap<String, String>,
+    /// Directory containing the loaded `ruitl.toml`. Relative paths in the
+    /// config resolve against it (see [`RuitlConfig::resolve_path`]); `None`
+    /// for configs built in code, which resolve against the CWD.
+    #[serde(skip)]
+    pub root: Option<PathBuf>,
 }
 
 /// A single static-site route. Used by `ruitl build`.
@@ -32,7 +43,8 @@ pub struct RouteConfig {
     pub component: String,
     /// Path to a JSON file containing the props for this route. Loaded and
     /// passed verbatim to the entry function so user code can deserialize
-    /// into its concrete `Props` type.
+    /// into its concrete `Props` type. Relative paths resolve against the
+    /// directory holding `ruitl.toml`.
     pub props_file: PathBuf,
 }
 
@@ -58,6 +70,60 @@ pub struct BuildConfig {
     pub out_dir: PathBuf,
     /// Source directory for the project
     pub src_dir: PathBuf,
+    /// Public URL the static site is served from (e.g.
+    /// `https://host/docs/`). When it has a path, `ruitl build` prefixes
+    /// root-relative `href`/`src` links with it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// How `ruitl build` maps a route's URL path to its output file.
+    #[serde(default)]
+    pub url_style: UrlStyle,
+    /// Header written after the `@generated` marker at the top of each
+    /// generated `mod.rs`. Defaults to the compiler's own header.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generated_header: Option<String>,
+    /// Rust edition passed to `rustfmt` for generated code. Defaults to
+    /// `2021`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+    /// Pre-render fully static markup into literals at compile time. See
+    /// `ruitl_compiler::CompileOptions::fold_static` for what it hides from
+    /// tree-walking helpers.
+    #[serde(default)]
+    pub fold_static: bool,
+    /// Report inline event handler attributes (`onclick="..."`) in
+    /// templates as compile warnings.
+    #[serde(default)]
+    pub lint_inline_handlers: bool,
+}
+
+/// URL-to-file mapping for static builds. `/` always writes `index.html`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlStyle {
+    /// `/blog/post` → `blog/post/index.html`, for pretty URLs on any static
+    /// file server.
+    #[default]
+    DirectoryIndex,
+    /// `/blog/post` → `blog/post.html`.
+    HtmlSuffix,
+}
+
+impl BuildConfig {
+    /// Compiler options derived from this build section, falling back to
+    /// the compiler defaults for unset fields.
+    pub fn compile_options(&self) -> ruitl_compiler::CompileOptions {
+        let mut options = ruitl_compiler::CompileOptions::default();
+        if let Some(header) = &self.generated_header {
+            options.header = header.clone();
+        }
+        if let Some(edition) = &self.edition {
+            options.edition = edition.clone();
+        }
+        options.fold_static = self.fold_static;
+        options.lint_inline_handlers = self.lint_inline_handlers;
+        options
+    }
 }
 
 impl Default for RuitlConfig {
@@ -73,24 +139,56 @@ impl Default for RuitlConfig {
                 template_dir: PathBuf::from("templates"),
                 out_dir: PathBuf::from("generated"),
                 src_dir: PathBuf::from("src"),
+                base_url: None,
+                url_style: UrlStyle::default(),
+                generated_header: None,
+                edition: None,
+                fold_static: false,
+                lint_inline_handlers: false,
             },
             routes: Vec::new(),
+            scripts: HashMap::new(),
+            root: None,
         }
     }
 }
 
 impl RuitlConfig {
-    /// Load configuration from a file
+    /// Load configuration from a file.
+    ///
+    /// A top-level `extends = "../base/ruitl.toml"` loads that file first
+    /// (relative to this one) and merges this file over it: tables merge key
+    /// by key, while scalars and arrays (including `[[routes]]`) replace.
+    /// Paths in the merged result resolve against this file, not the base.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let content = fs::read_to_string(path.as_ref())
-            .map_err(|e| RuitlError::config(format!("Failed to read config file: {}", e)))?;
-
-        let config: RuitlConfig = toml::from_str(&content)
+        let value = load_toml_file(path.as_ref(), &env_lookup, &mut Vec::new())?;
+        let mut config: RuitlConfig = value
+            .try_into()
             .map_err(|e| RuitlError::config(format!("Failed to parse config file: {}", e)))?;
-
+        config.root = path.as_ref().parent().map(Path::to_path_buf);
         Ok(config)
     }
 
+    /// Parse configuration from TOML text, expanding environment variables
+    /// in its string values; see [`expand_env_vars`]. Relative paths,
+    /// including `extends`, resolve against the working directory, since
+    /// there is no config file to anchor them.
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        Self::load_from_str_with(content, env_lookup)
+    }
+
+    /// [`load_from_str`](Self::load_from_str) with variables resolved by
+    /// `lookup` instead of the process environment.
+    pub fn load_from_str_with<F>(content: &str, lookup: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let value = load_toml_str(content, Path::new("."), &lookup, &mut Vec::new())?;
+        value
+            .try_into()
+            .map_err(|e| RuitlError::config(format!("Failed to parse config file: {}", e)))
+    }
+
     /// Save configuration to a file
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let content = toml::to_string_pretty(self)
@@ -102,6 +200,31 @@ impl RuitlConfig {
         Ok(())
     }
 
+    /// Resolve a path from the config against the config file's directory.
+    /// Absolute paths are returned unchanged.
+    pub fn resolve_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let path = path.as_ref();
+        match &self.root {
+            Some(root) if path.is_relative() => root.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    /// `build.template_dir`, resolved with [`resolve_path`](Self::resolve_path)
+    pub fn template_dir(&self) -> PathBuf {
+        self.resolve_path(&self.build.template_dir)
+    }
+
+    /// `build.out_dir`, resolved with [`resolve_path`](Self::resolve_path)
+    pub fn out_dir(&self) -> PathBuf {
+        self.resolve_path(&self.build.out_dir)
+    }
+
+    /// `build.src_dir`, resolved with [`resolve_path`](Self::resolve_path)
+    pub fn src_dir(&self) -> PathBuf {
+        self.resolve_path(&self.build.src_dir)
+    }
+
     /// Validate the configuration
     pub fn validate(&self) -> Result<()> {
         // Basic validation
@@ -121,6 +244,173 @@ impl RuitlConfig {
     }
 }
 
+/// Variable lookup against the process environment.
+fn env_lookup(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Resolves `${VAR}` names while loading a config.
+type EnvLookup<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// Read one config file and resolve its `extends` chain. `chain` holds the
+/// files currently being loaded, to report cycles.
+fn load_toml_file(path: &Path, lookup: EnvLookup, chain: &mut Vec<PathBuf>) -> Result<toml::Value> {
+    let key = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&key) {
+        let cycle: Vec<String> = chain
+            .iter()
+            .chain(std::iter::once(&key))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(RuitlError::config(format!(
+            "Cyclic `extends` in config: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| {
+        RuitlError::config(format!(
+            "Failed to read config file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    chain.push(key);
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let value = load_toml_str(&content, dir, lookup, chain);
+    chain.pop();
+    value
+}
+
+fn load_toml_str(
+    content: &str,
+    dir: &Path,
+    lookup: EnvLookup,
+    chain: &mut Vec<PathBuf>,
+) -> Result<toml::Value> {
+    let mut value: toml::Value = toml::from_str(content)
+        .map_err(|e| RuitlError::config(format!("Failed to parse config file: {}", e)))?;
+    // `[scripts]` holds shell commands, whose `${VAR}`s belong to the shell
+    // at run time rather than to the environment at load time.
+    if let Some(table) = value.as_table_mut() {
+        for (key, item) in table.iter_mut() {
+            if key != "scripts" {
+                expand_env_in_value(item, lookup)?;
+            }
+        }
+    }
+
+    let Some(extends) = value
+        .as_table_mut()
+        .and_then(|table| table.remove("extends"))
+    else {
+        return Ok(value);
+    };
+    let extends = extends
+        .as_str()
+        .ok_or_else(|| RuitlError::config("`extends` must be a path string"))?;
+    let mut base = load_toml_file(&dir.join(extends), lookup, chain)?;
+    merge_toml(&mut base, value);
+    Ok(base)
+}
+
+/// Merge `overlay` into `base`: tables merge recursively, anything else in
+/// `overlay` replaces the value in `base`.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_toml(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Run [`expand_env_vars`] over every string value in a parsed TOML value,
+/// leaving keys, comments and non-string values alone.
+fn expand_env_in_value(value: &mut toml::Value, lookup: EnvLookup) -> Result<()> {
+    match value {
+        toml::Value::String(text) if text.contains('$') => {
+            *text = expand_env_vars(text, lookup)?;
+        }
+        toml::Value::Array(items) => {
+            for item in items {
+                expand_env_in_value(item, lookup)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                expand_env_in_value(item, lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace `${VAR}` in a config string value with the value `lookup`
+/// returns for `VAR`, or with `default` for `${VAR:-default}` when it
+/// returns `None`. `$${` writes a literal `${`. Fails on an unset variable
+/// without a default, an invalid name, or a missing `}`.
+pub fn expand_env_vars<F>(content: &str, lookup: F) -> Result<String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find('$') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start..];
+        if let Some(escaped) = after.strip_prefix("$${") {
+            out.push_str("${");
+            rest = escaped;
+            continue;
+        }
+        let Some(body) = after.strip_prefix("${") else {
+            out.push('$');
+            rest = &after[1..];
+            continue;
+        };
+        let end = body.find('}').ok_or_else(|| {
+            RuitlError::config(format!(
+                "Unterminated `${{` in config: `{}`",
+                after.lines().next().unwrap_or(after)
+            ))
+        })?;
+        let (name, default) = match body[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&body[..end], None),
+        };
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(RuitlError::config(format!(
+                "Invalid environment variable name `{}` in config",
+                name
+            )));
+        }
+        match (lookup(name), default) {
+            (Some(value), _) => out.push_str(&value),
+            (None, Some(default)) => out.push_str(default),
+            (None, None) => {
+                return Err(RuitlError::config(format!(
+                "Environment variable `{}` is not set and has no default (use `${{{}:-default}}`)",
+                name, name
+            )))
+            }
+        }
+        rest = &body[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +450,177 @@ mod tests {
             loaded_config.project.version
         );
     }
+
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| (name == "SITE_URL").then(|| "https://example.com".to_string());
+
+        let expanded = expand_env_vars(
+            "base_url = \"${SITE_URL}/docs\"\nname = \"${SITE_NAME:-demo}\"\ncost = \"$5 $${HOME}\"",
+            lookup,
+        )
+        .unwrap();
+        assert_eq!(
+            expanded,
+            "base_url = \"https://example.com/docs\"\nname = \"demo\"\ncost = \"$5 ${HOME}\""
+        );
+
+        let err = expand_env_vars("secret = \"${SESSION_SECRET}\"", lookup).unwrap_err();
+        assert!(
+            err.to_string().contains("`SESSION_SECRET` is not set"),
+            "{}",
+            err
+        );
+        assert!(expand_env_vars("x = \"${OPEN\"", lookup).is_err());
+        assert!(expand_env_vars("x = \"${1BAD}\"", lookup).is_err());
+    }
+
+    #[test]
+    fn test_load_from_str_reads_environment() {
+        let lookup =
+            |name: &str| (name == "RUITL_CONFIG_TEST_NAME").then(|| "from-env".to_string());
+        let config = RuitlConfig::load_from_str_with(
+            "[project]\nname = \"${RUITL_CONFIG_TEST_NAME}\"\nversion = \"${RUITL_CONFIG_TEST_UNSET:-0.2.0}\"\nauthors = []\n\n[build]\ntemplate_dir = \"templates\"\nout_dir = \"generated\"\nsrc_dir = \"src\"\n",
+            lookup,
+        )
+        .unwrap();
+        assert_eq!(config.project.name, "from-env");
+        assert_eq!(config.project.version, "0.2.0");
+    }
+
+    #[test]
+    fn test_env_expansion_only_touches_string_values() {
+        let lookup = |name: &str| (name == "TITLE").then(|| r#"say "hi" \ bye"#.to_string());
+        let config = RuitlConfig::load_from_str_with(
+            r#"
+# Set ${UNSET_IN_COMMENT} to override.
+[project]
+name = "${TITLE}"
+version = '0.1.0'
+authors = ["${AUTHOR:-anon}"]
+
+[build]
+template_dir = "templates"
+out_dir = "generated"
+src_dir = "src"
+"#,
+            lookup,
+        )
+        .unwrap();
+        assert_eq!(config.project.name, r#"say "hi" \ bye"#);
+        assert_eq!(config.project.authors, ["anon"]);
+    }
+
+    #[test]
+    fn test_scripts_are_not_env_expanded() {
+        let config = RuitlConfig::load_from_str_with(
+            r#"
+[project]
+name = "app"
+version = "0.1.0"
+authors = []
+
+[build]
+template_dir = "templates"
+out_dir = "generated"
+src_dir = "src"
+
+[scripts]
+deploy = "rsync -a dist/ ${DEPLOY_HOST}:/srv/site"
+"#,
+            |_: &str| None,
+        )
+        .unwrap();
+        assert_eq!(
+            config.scripts["deploy"],
+            "rsync -a dist/ ${DEPLOY_HOST}:/srv/site"
+        );
+    }
+
+    #[test]
+    fn test_extends_merges_base_config() {
+        let temp_dir = tempdir().unwrap();
+        let base_dir = temp_dir.path().join("base");
+        let app_dir = temp_dir.path().join("app");
+        fs::create_dir_all(&base_dir).unwrap();
+        fs::create_dir_all(&app_dir).unwrap();
+
+        fs::write(
+            base_dir.join("ruitl.toml"),
+            r#"
+[project]
+name = "shared"
+version = "1.0.0"
+authors = ["Team"]
+
+[build]
+template_dir = "templates"
+out_dir = "generated"
+src_dir = "src"
+base_url = "/docs"
+
+[scripts]
+lint = "cargo clippy"
+"#,
+        )
+        .unwrap();
+        let child = app_dir.join("ruitl.toml");
+        fs::write(
+            &child,
+            r#"
+extends = "../base/ruitl.toml"
+
+[project]
+name = "app"
+
+[build]
+out_dir = "dist"
+
+[scripts]
+serve = "cargo run"
+"#,
+        )
+        .unwrap();
+
+        let config = RuitlConfig::from_file(&child).unwrap();
+        assert_eq!(config.project.name, "app");
+        assert_eq!(config.project.version, "1.0.0");
+        assert_eq!(config.project.authors, vec!["Team".to_string()]);
+        assert_eq!(config.build.out_dir, PathBuf::from("dist"));
+        assert_eq!(config.build.base_url.as_deref(), Some("/docs"));
+        assert_eq!(config.scripts.len(), 2);
+        assert_eq!(config.out_dir(), app_dir.join("dist"));
+    }
+
+    #[test]
+    fn test_cyclic_extends_is_an_error() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("a.toml"), "extends = \"b.toml\"\n").unwrap();
+        fs::write(temp_dir.path().join("b.toml"), "extends = \"a.toml\"\n").unwrap();
+
+        let err = RuitlConfig::from_file(temp_dir.path().join("a.toml")).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("Cyclic `extends`"), "{}", message);
+        assert!(message.contains("a.toml -> "), "{}", message);
+    }
+
+    #[test]
+    fn test_paths_resolve_relative_to_config_file() {
+        let temp_dir = tempdir().unwrap();
+        let site = temp_dir.path().join("site");
+        fs::create_dir_all(&site).unwrap();
+        let config_path = site.join("ruitl.toml");
+
+        let mut config = RuitlConfig::default();
+        config.build.out_dir = temp_dir.path().join("dist");
+        config.save_to_file(&config_path).unwrap();
+
+        let loaded = RuitlConfig::from_file(&config_path).unwrap();
+        assert_eq!(loaded.root.as_deref(), Some(site.as_path()));
+        assert_eq!(loaded.src_dir(), site.join("src"));
+        assert_eq!(loaded.template_dir(), site.join("templates"));
+        assert_eq!(loaded.out_dir(), temp_dir.path().join("dist"));
+
+        assert_eq!(RuitlConfig::default().src_dir(), PathBuf::from("src"));
+    }
 }
@@ -7,7 +7,8 @@
 //!   and reloads the page on each tick. Scaffolded projects inject this
 //!   script tag when `--with-hot-reload` is set.
 //! - `GET /ruitl/reload` — Server-Sent Events endpoint. Emits `event: reload`
-//!   after each successful template recompile.
+//!   after each successful template recompile, and a `: keep-alive` comment
+//!   whenever the stream has been idle for the heartbeat interval.
 //!
 //! Why SSE, not WebSocket: SSE needs no extra dependency (one-way text
 //! events over plain HTTP), auto-reconnects, and is enough for
@@ -33,7 +34,6 @@ use tokio::sync::broadcast;
 const RELOAD_JS_TEMPLATE: &str = r#"(() => {
   const es = new EventSource("__RUITL_RELOAD_URL__");
   es.addEventListener("reload", () => window.location.reload());
-  es.addEventListener("ping", () => {}); // keep-alive noop
   window.addEventListener("beforeunload", () => es.close());
 })();
 "#;
@@ -47,6 +47,9 @@ pub struct DevOptions {
     pub reload_port: u16,
     /// Verbose logging of every recompile / SSE event.
     pub verbose: bool,
+    /// How long an SSE stream may sit idle before a keep-alive comment is
+    /// sent, so proxies don't close the connection.
+    pub heartbeat: Duration,
 }
 
 impl Default for DevOptions {
@@ -54,6 +57,7 @@ impl Default for DevOptions {
         Self {
             reload_port: 35729,
             verbose: false,
+            heartbeat: Duration::from_secs(30),
         }
     }
 }
@@ -134,11 +138,12 @@ pub async fn run_dev(src_dir: &Path, opts: DevOptions) -> Result<()> {
     let make_svc = make_service_fn(move |_| {
         let bus = Arc::clone(&bus_for_server);
         let port = opts.reload_port;
+        let heartbeat = opts.heartbeat;
         async move {
             let bus = bus.clone();
             Ok::<_, Infallible>(service_fn(move |req| {
                 let bus = bus.clone();
-                async move { handle_request(req, bus, port).await }
+                async move { handle_request(req, bus, port, heartbeat).await }
             }))
         }
     });
@@ -205,10 +210,11 @@ async fn handle_request(
     req: Request<Body>,
     bus: Arc<ReloadBus>,
     port: u16,
+    heartbeat: Duration,
 ) -> std::result::Result<Response<Body>, Infallible> {
     match (req.method(), req.uri().path()) {
         (&Method::GET, "/ruitl/reload.js") => Ok(reload_js_response(port)),
-        (&Method::GET, "/ruitl/reload") => Ok(sse_response(bus.subscribe())),
+        (&Method::GET, "/ruitl/reload") => Ok(sse_response(bus.subscribe(), heartbeat)),
         _ => Ok(not_found()),
     }
 }
@@ -230,45 +236,42 @@ fn reload_js_response(port: u16) -> Response<Body> {
         .unwrap()
 }
 
-/// Wrap a `broadcast::Receiver<()>` as a stream of SSE-formatted frames.
-/// Merges the reload channel with a 30s ping ticker (so proxies don't close
-/// the connection) into a single HTTP response body.
-fn sse_response(rx: broadcast::Receiver<()>) -> Response<Body> {
+/// SSE frames for one client: a `:connected` hello, then an `event: reload`
+/// frame per reload tick, with a `: keep-alive` comment whenever no frame
+/// has been sent for `heartbeat` (so proxies don't prune idle connections).
+/// Ends when the bus is dropped; lagged ticks are skipped, since the browser
+/// only needs one reload.
+fn sse_frames(
+    rx: broadcast::Receiver<()>,
+    heartbeat: Duration,
+) -> impl futures::Stream<Item = hyper::body::Bytes> {
     use futures::stream::StreamExt;
-    use tokio_stream::wrappers::{BroadcastStream, IntervalStream};
-
-    // Map each reload tick into an SSE `reload` frame, dropping lag errors
-    // (browser reconnects automatically on close).
-    let reloads = BroadcastStream::new(rx).filter_map(|item| async move {
-        match item {
-            Ok(_) => Some(hyper::body::Bytes::from(
-                "event: reload\ndata: \n\n".to_string(),
-            )),
-            Err(_) => None,
-        }
-    });
 
-    // Keep-alive pings every 30s so intermediaries don't prune idle
-    // connections.
-    let mut interval = tokio::time::interval(Duration::from_secs(30));
-    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
-    let pings = IntervalStream::new(interval)
-        .map(|_| hyper::body::Bytes::from("event: ping\ndata: \n\n".to_string()));
-
-    // Prime with an immediate hello frame so clients know they connected.
-    let hello = futures::stream::once(async {
-        hyper::body::Bytes::from(":connected\n\n".to_string())
+    let hello = futures::stream::once(async { hyper::body::Bytes::from(":connected\n\n") });
+    let events = futures::stream::unfold(rx, move |mut rx| async move {
+        loop {
+            let frame = match tokio::time::timeout(heartbeat, rx.recv()).await {
+                Ok(Ok(())) => "event: reload\ndata: \n\n",
+                Ok(Err(broadcast::error::RecvError::Lagged(_))) => continue,
+                Ok(Err(broadcast::error::RecvError::Closed)) => return None,
+                Err(_) => ": keep-alive\n\n",
+            };
+            return Some((hyper::body::Bytes::from(frame), rx));
+        }
     });
+    hello.chain(events)
+}
 
-    let merged = hello
-        .chain(futures::stream::select(reloads, pings))
-        .map(Ok::<_, Infallible>);
+/// Serve [`sse_frames`] as a `text/event-stream` response.
+fn sse_response(rx: broadcast::Receiver<()>, heartbeat: Duration) -> Response<Body> {
+    use futures::stream::StreamExt;
 
+    let frames = sse_frames(rx, heartbeat).map(Ok::<_, Infallible>);
     Response::builder()
         .header("content-type", "text/event-stream")
         .header("cache-control", "no-cache")
         .header("access-control-allow-origin", "*")
-        .body(Body::wrap_stream(merged))
+        .body(Body::wrap_stream(frames))
         .unwrap()
 }
 
@@ -307,6 +310,24 @@ mod tests {
         bus.fire(); // no one subscribed — must not panic
     }
 
+    #[tokio::test]
+    async fn sse_sends_keep_alive_when_idle() {
+        use futures::stream::StreamExt;
+
+        let bus = ReloadBus::new();
+        let mut frames = Box::pin(sse_frames(bus.subscribe(), Duration::from_millis(20)));
+        let limit = Duration::from_secs(1);
+
+        let hello = tokio::time::timeout(limit, frames.next()).await.unwrap();
+        assert_eq!(hello.unwrap(), ":connected\n\n");
+        let idle = tokio::time::timeout(limit, frames.next()).await.unwrap();
+        assert_eq!(idle.unwrap(), ": keep-alive\n\n");
+
+        bus.fire();
+        let reload = tokio::time::timeout(limit, frames.next()).await.unwrap();
+        assert_eq!(reload.unwrap(), "event: reload\ndata: \n\n");
+    }
+
     #[test]
     fn script_tag_embeds_port() {
         let t = reload_script_tag(12345);
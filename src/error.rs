@@ -45,6 +45,10 @@ pub enum RuitlError {
     #[error("Static generation error: {message}")]
     StaticGen { message: String },
 
+    /// A render stopped because its cancellation token was triggered
+    #[error("Render cancelled: {message}")]
+    Cancelled { message: String },
+
     /// Serialization errors
     #[error("Serialization error: {0}")]
     Serde(#[from] serde_json::Error),
@@ -80,6 +84,18 @@ pub enum RuitlError {
     /// Generic errors
     #[error("Error: {message}")]
     Generic { message: String },
+
+    /// Several independent failures reported together, one per line
+    #[error("{}", display_multiple(.0))]
+    Multiple(Vec<RuitlError>),
+}
+
+fn display_multiple(errors: &[RuitlError]) -> String {
+    let mut out = format!("{} error(s):", errors.len());
+    for (i, err) in errors.iter().enumerate() {
+        out.push_str(&format!("\n  {}. {}", i + 1, err));
+    }
+    out
 }
 
 impl From<ruitl_compiler::CompileError> for RuitlError {
@@ -179,6 +195,23 @@ impl RuitlError {
         }
     }
 
+    /// Create a new cancellation error
+    pub fn cancelled<S: Into<String>>(message: S) -> Self {
+        Self::Cancelled {
+            message: message.into(),
+        }
+    }
+
+    /// Aggregate several errors into one. A single error is returned as-is
+    /// rather than wrapped.
+    pub fn multiple(mut errors: Vec<RuitlError>) -> Self {
+        if errors.len() == 1 {
+            errors.remove(0)
+        } else {
+            Self::Multiple(errors)
+        }
+    }
+
     /// Get the error message
     pub fn message(&self) -> String {
         self.to_string()
@@ -223,6 +256,16 @@ impl RuitlError {
     pub fn is_server(&self) -> bool {
         matches!(self, Self::Server { .. })
     }
+
+    /// Check if this is a cancellation error
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// Check if this is an aggregate of several errors
+    pub fn is_multiple(&self) -> bool {
+        matches!(self, Self::Multiple(_))
+    }
 }
 
 /// Result type alias for RUITL operations
@@ -342,4 +385,21 @@ mod tests {
         assert!(RuitlError::server("test").is_server());
         assert!(RuitlError::generic("test").message().contains("test"));
     }
+
+    #[test]
+    fn test_multiple_error_display_indexes_each_error() {
+        let err = RuitlError::multiple(vec![
+            RuitlError::parse("a.ruitl: unexpected '}'"),
+            RuitlError::config("missing props_file"),
+        ]);
+        assert!(err.is_multiple());
+        assert_eq!(
+            err.to_string(),
+            "2 error(s):\n  1. Parse error: a.ruitl: unexpected '}'\n  \
+             2. Configuration error: missing props_file"
+        );
+
+        let single = RuitlError::multiple(vec![RuitlError::render("boom")]);
+        assert!(single.is_render());
+    }
 }
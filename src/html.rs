@@ -1,8 +1,19 @@
 //! HTML rendering and manipulation utilities
 
 use crate::error::{Result, RuitlError};
-use html_escape::{encode_quoted_attribute, encode_text};
+use ruitl_compiler::markup::{escape_html, is_void_element, preserves_whitespace};
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::fmt::{self, Display, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+fn prefixed_attr_name(prefix: &str, key: &str) -> String {
+    if key.starts_with(prefix) {
+        key.to_string()
+    } else {
+        format!("{}{}", prefix, key)
+    }
+}
 
 /// Represents an HTML element with attributes and children.
 ///
@@ -15,6 +26,9 @@ pub struct HtmlElement {
     pub attributes: Vec<(String, HtmlAttribute)>,
     pub children: Vec<Html>,
     pub self_closing: bool,
+    /// Render nothing when every child is empty; see
+    /// [`HtmlElement::omit_if_empty`]
+    pub omit_if_empty: bool,
 }
 
 /// Represents an HTML attribute with optional value
@@ -51,6 +65,7 @@ impl HtmlElement {
             attributes: Vec::new(),
             children: Vec::new(),
             self_closing: false,
+            omit_if_empty: false,
         }
     }
 
@@ -61,6 +76,7 @@ impl HtmlElement {
             attributes: Vec::new(),
             children: Vec::new(),
             self_closing: true,
+            omit_if_empty: false,
         }
     }
 
@@ -75,12 +91,59 @@ impl HtmlElement {
         self
     }
 
+    /// Add an inline event handler attribute (`on("click", "...")` →
+    /// `onclick="..."`). `event` is the bare event name and always gets the
+    /// `on` prefix, so `on("online", ..)` writes `ononline`.
+    ///
+    /// The handler is stored as a plain attribute value and goes through
+    /// [`escape_attribute`] at render time, so quotes and angle brackets in
+    /// the script cannot break out of the attribute.
+    pub fn on<E: AsRef<str>, H: Into<String>>(self, event: E, handler: H) -> Self {
+        self.attr(format!("on{}", event.as_ref()), handler)
+    }
+
+    /// Add a `data-*` attribute (`data("user-id", "5")` →
+    /// `data-user-id="5"`). A leading `data-` in `key` is accepted.
+    pub fn data<K: AsRef<str>, V: Into<String>>(self, key: K, value: V) -> Self {
+        let name = prefixed_attr_name("data-", key.as_ref());
+        self.attr(name, value)
+    }
+
+    /// Add several `data-*` attributes in iteration order. Pass an ordered
+    /// collection (`Vec`, `BTreeMap`) for deterministic output.
+    pub fn data_map<I, K, V>(self, data: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        data.into_iter()
+            .fold(self, |element, (key, value)| element.data(key, value))
+    }
+
+    /// Add an `aria-*` attribute (`aria("label", "Close")` →
+    /// `aria-label="Close"`). A leading `aria-` in `key` is accepted.
+    pub fn aria<K: AsRef<str>, V: Into<String>>(self, key: K, value: V) -> Self {
+        let name = prefixed_attr_name("aria-", key.as_ref());
+        self.attr(name, value)
+    }
+
     /// Add a boolean attribute
     pub fn bool_attr<K: Into<String>>(mut self, key: K) -> Self {
         self.attributes.push((key.into(), HtmlAttribute::Boolean));
         self
     }
 
+    /// Add a bare boolean attribute (`disabled`) only when `condition`
+    /// holds — the runtime half of `disabled?={cond}` in templates.
+    pub fn bool_attr_if<K: Into<String>>(self, key: K, condition: bool) -> Self {
+        if condition {
+            self.bool_attr(key)
+        } else {
+            self
+        }
+    }
+
     /// Add a class attribute (merged with any existing `class` entry)
     pub fn class<S: Into<String>>(mut self, class: S) -> Self {
         let class_name = class.into();
@@ -119,6 +182,53 @@ impl HtmlElement {
         self
     }
 
+    /// Add a class only when `condition` holds — the runtime half of the
+    /// `class:NAME={cond}` template directive.
+    pub fn class_if<S: Into<String>>(self, class: S, condition: bool) -> Self {
+        if condition {
+            self.class(class)
+        } else {
+            self
+        }
+    }
+
+    /// Add one inline style declaration (`style("color", "red")`), appended
+    /// to any existing `style` attribute with `;`. The value is escaped like
+    /// any other attribute value when rendered.
+    pub fn style<P: AsRef<str>, V: AsRef<str>>(mut self, property: P, value: V) -> Self {
+        let declaration = format!("{}:{}", property.as_ref().trim(), value.as_ref().trim());
+        let existing = self
+            .attributes
+            .iter_mut()
+            .find(|(k, _)| k == "style")
+            .map(|(_, v)| v);
+        match existing {
+            Some(HtmlAttribute::Value(existing)) if !existing.trim().is_empty() => {
+                let trimmed = existing.trim_end().trim_end_matches(';').to_string();
+                *existing = format!("{};{}", trimmed, declaration);
+            }
+            Some(other) => *other = HtmlAttribute::Value(declaration),
+            None => {
+                self.attributes
+                    .push(("style".to_string(), HtmlAttribute::Value(declaration)));
+            }
+        }
+        self
+    }
+
+    /// Add several inline style declarations in iteration order. Pass an
+    /// ordered collection (`Vec`, `BTreeMap`) for deterministic output.
+    pub fn styles<I, P, V>(self, styles: I) -> Self
+    where
+        I: IntoIterator<Item = (P, V)>,
+        P: AsRef<str>,
+        V: AsRef<str>,
+    {
+        styles.into_iter().fold(self, |element, (property, value)| {
+            element.style(property, value)
+        })
+    }
+
     /// Add an ID attribute (replaces any existing `id` entry)
     pub fn id<S: Into<String>>(mut self, id: S) -> Self {
         self.attributes.retain(|(k, _)| k != "id");
@@ -133,6 +243,12 @@ impl HtmlElement {
         self
     }
 
+    /// Insert a child before the existing ones
+    pub fn prepend_child(mut self, child: Html) -> Self {
+        self.children.insert(0, child);
+        self
+    }
+
     /// Add an attribute conditionally
     pub fn attr_if<K: Into<String>, V: Into<String>>(
         mut self,
@@ -173,7 +289,7 @@ impl HtmlElement {
 
     /// Add raw HTML content as a child
     pub fn raw<S: Into<String>>(mut self, html: S) -> Self {
-        self.children.push(Html::Raw(html.into()));
+        self.children.push(Html::raw(html));
         self
     }
 
@@ -182,10 +298,137 @@ impl HtmlElement {
         !self.children.is_empty()
     }
 
+    /// The element's tag name
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Rename the element, e.g. deprecated `<center>` to `<div>`. Renaming
+    /// away from a void tag (`img`, `br`, …) drops its self-closing form, and
+    /// renaming to one makes the element void, so children are not rendered.
+    pub fn set_tag<S: Into<String>>(&mut self, tag: S) {
+        if is_void_element(&self.tag) {
+            self.self_closing = false;
+        }
+        self.tag = tag.into();
+    }
+
+    /// Render nothing at all, wrapper included, when the element ends up
+    /// with no non-empty children — e.g. a `<div>` around an `if` that
+    /// produced [`Html::Empty`]. Void elements are never omitted.
+    pub fn omit_if_empty(mut self) -> Self {
+        self.omit_if_empty = true;
+        self
+    }
+
+    /// Whether [`omit_if_empty`](Self::omit_if_empty) applies right now
+    fn is_omitted(&self) -> bool {
+        self.omit_if_empty && !self.is_self_closing() && self.children.iter().all(Html::is_empty)
+    }
+
     /// Check if this element is self-closing
     pub fn is_self_closing(&self) -> bool {
         self.self_closing || is_void_element(&self.tag)
     }
+
+    /// Value of the first attribute named `name`. Boolean attributes read as
+    /// `""`; list-valued attributes (from [`classes`](Self::classes)) have no
+    /// single string and read as `None` — use [`has_class`](Self::has_class).
+    pub fn get_attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == name)
+            .and_then(|(_, v)| match v {
+                HtmlAttribute::Value(value) => Some(value.as_str()),
+                HtmlAttribute::Boolean => Some(""),
+                HtmlAttribute::List(_) => None,
+            })
+    }
+
+    /// Check whether an attribute named `name` is present, whatever its value
+    pub fn has_attr(&self, name: &str) -> bool {
+        self.attributes.iter().any(|(k, _)| k == name)
+    }
+
+    /// Check whether `class` appears in the element's whitespace-separated
+    /// class list
+    pub fn has_class(&self, class: &str) -> bool {
+        self.attributes
+            .iter()
+            .filter(|(k, _)| k == "class")
+            .any(|(_, v)| match v {
+                HtmlAttribute::Value(value) => value.split_whitespace().any(|c| c == class),
+                HtmlAttribute::List(list) => list
+                    .iter()
+                    .any(|entry| entry.split_whitespace().any(|c| c == class)),
+                HtmlAttribute::Boolean => false,
+            })
+    }
+}
+
+/// Buffers kept per thread by [`Html::render_pooled`].
+const RENDER_POOL_SIZE: usize = 8;
+
+/// Buffers that grew past this are dropped instead of pooled, so one huge
+/// page doesn't pin its allocation for the life of the thread.
+const RENDER_POOL_MAX_CAPACITY: usize = 1 << 20;
+
+thread_local! {
+    static RENDER_POOL: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Rendered output backed by a pooled buffer; see [`Html::render_pooled`].
+#[derive(Debug)]
+pub struct PooledHtml {
+    buf: String,
+}
+
+impl PooledHtml {
+    /// The rendered HTML
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    /// Capacity of the underlying buffer
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Take ownership of the output; the buffer is not returned to the pool
+    pub fn into_string(mut self) -> String {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+impl std::ops::Deref for PooledHtml {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.buf
+    }
+}
+
+impl Display for PooledHtml {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.buf)
+    }
+}
+
+impl Drop for PooledHtml {
+    fn drop(&mut self) {
+        let mut buf = std::mem::take(&mut self.buf);
+        if buf.capacity() == 0 || buf.capacity() > RENDER_POOL_MAX_CAPACITY {
+            return;
+        }
+        buf.clear();
+        // `try_with` because the pool may already be gone during thread exit.
+        let _ = RENDER_POOL.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < RENDER_POOL_SIZE {
+                pool.push(buf);
+            }
+        });
+    }
 }
 
 /// Apply the `minify-html` pass when the `minify` feature is on. No-op
@@ -212,11 +455,11 @@ impl HtmlAttribute {
     /// Render the attribute as a string
     pub fn render(&self) -> String {
         match self {
-            HtmlAttribute::Value(value) => format!("\"{}\"", encode_quoted_attribute(value)),
+            HtmlAttribute::Value(value) => format!("\"{}\"", escape_attribute(value)),
             HtmlAttribute::Boolean => String::new(),
             HtmlAttribute::List(list) => {
                 let joined = list.join(" ");
-                format!("\"{}\"", encode_quoted_attribute(&joined))
+                format!("\"{}\"", escape_attribute(&joined))
             }
         }
     }
@@ -233,9 +476,13 @@ impl Html {
         Html::Text(content.into())
     }
 
-    /// Create raw HTML content
+    /// Create raw HTML content. The markup is trusted as-is; debug builds
+    /// warn on stderr when its tags look unbalanced (see
+    /// [`set_raw_markup_check`]).
     pub fn raw<S: Into<String>>(content: S) -> Self {
-        Html::Raw(content.into())
+        let content = content.into();
+        warn_unbalanced_raw(&content);
+        Html::Raw(content)
     }
 
     /// Create an element
@@ -297,6 +544,190 @@ impl Html {
         maybe_minify(s)
     }
 
+    /// Render into a buffer drawn from a thread-local pool. Dropping the
+    /// returned [`PooledHtml`] clears the buffer and hands it back, so a
+    /// server thread rendering many pages settles into reusing a few
+    /// warm allocations. Use [`PooledHtml::into_string`] to keep the output.
+    pub fn render_pooled(&self) -> PooledHtml {
+        let mut buf = RENDER_POOL
+            .with(|pool| pool.borrow_mut().pop())
+            .unwrap_or_default();
+        buf.reserve(self.len_hint());
+        let _ = self.render_to(&mut buf);
+        #[cfg(feature = "minify")]
+        {
+            buf = maybe_minify(buf);
+        }
+        PooledHtml { buf }
+    }
+
+    /// Serialize the tree for client-side code, using a small stable schema
+    /// rather than the enum layout:
+    ///
+    /// - element → `{"tag": "a", "attrs": {"href": "/x"}, "children": [...]}`,
+    ///   with boolean attributes as `true` and class lists joined by spaces
+    /// - text → a JSON string (unescaped)
+    /// - raw HTML → `{"raw": "..."}`
+    ///
+    /// Fragments are flattened into their parent's `children` and empty nodes
+    /// are dropped. At the top level a fragment becomes an array and
+    /// [`Html::Empty`] becomes `null`.
+    pub fn to_dom_json(&self) -> serde_json::Value {
+        match self {
+            Html::Empty => serde_json::Value::Null,
+            Html::Fragment(_) => {
+                let mut nodes = Vec::new();
+                self.push_dom_json(&mut nodes);
+                serde_json::Value::Array(nodes)
+            }
+            Html::Text(text) => serde_json::Value::String(text.clone()),
+            Html::Raw(raw) => serde_json::json!({ "raw": raw }),
+            Html::Element(element) => {
+                let attrs: serde_json::Map<String, serde_json::Value> = element
+                    .attributes
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = match value {
+                            HtmlAttribute::Value(v) => serde_json::Value::String(v.clone()),
+                            HtmlAttribute::Boolean => serde_json::Value::Bool(true),
+                            HtmlAttribute::List(list) => serde_json::Value::String(list.join(" ")),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect();
+                let mut children = Vec::new();
+                for child in &element.children {
+                    child.push_dom_json(&mut children);
+                }
+                serde_json::json!({
+                    "tag": element.tag,
+                    "attrs": attrs,
+                    "children": children,
+                })
+            }
+        }
+    }
+
+    /// Append this node's [`to_dom_json`](Self::to_dom_json) form to `out`,
+    /// splicing fragments and skipping empty nodes.
+    fn push_dom_json(&self, out: &mut Vec<serde_json::Value>) {
+        match self {
+            Html::Empty => {}
+            Html::Fragment(nodes) => {
+                for node in nodes {
+                    node.push_dom_json(out);
+                }
+            }
+            node => out.push(node.to_dom_json()),
+        }
+    }
+
+    /// Render a canonical form for cache keys. Attributes are sorted by name
+    /// (and class names within `class`), and whitespace runs in text collapse to a
+    /// single space outside `<pre>`, `<textarea>`, `<script>` and `<style>`,
+    /// so trees differing only in those respects give the same string.
+    /// Unlike [`render`](Self::render), author order is not kept and the
+    /// output is never minified.
+    pub fn canonical_string(&self) -> String {
+        let mut out = String::new();
+        let _ = self.canonicalized(false).render_to(&mut out);
+        out
+    }
+
+    /// Render with a built-in minifying pass, whether or not the `minify`
+    /// feature is on: HTML comments are dropped, whitespace runs in text
+    /// collapse to one space, and whitespace-only text spanning a line break
+    /// (indentation between tags) is removed. `<pre>`, `<textarea>`,
+    /// `<script>` and `<style>` contents are untouched. With `minify` on,
+    /// the result also goes through `minify_html` like [`render`](Self::render).
+    pub fn render_minified(&self) -> String {
+        let mut out = String::new();
+        let _ = self.minified(false).render_to(&mut out);
+        maybe_minify(out)
+    }
+
+    /// Copy of the tree in [`render_minified`](Self::render_minified) form.
+    fn minified(&self, preserve_whitespace: bool) -> Html {
+        match self {
+            Html::Element(element) => {
+                let preserve = preserve_whitespace || preserves_whitespace(&element.tag);
+                Html::Element(HtmlElement {
+                    tag: element.tag.clone(),
+                    attributes: element.attributes.clone(),
+                    children: element
+                        .children
+                        .iter()
+                        .map(|child| child.minified(preserve))
+                        .collect(),
+                    self_closing: element.self_closing,
+                    omit_if_empty: element.omit_if_empty,
+                })
+            }
+            Html::Fragment(nodes) => Html::Fragment(
+                nodes
+                    .iter()
+                    .map(|node| node.minified(preserve_whitespace))
+                    .collect(),
+            ),
+            Html::Text(text) if !preserve_whitespace => {
+                if text.trim().is_empty() && text.contains('\n') {
+                    Html::Empty
+                } else {
+                    Html::Text(collapse_whitespace(text))
+                }
+            }
+            Html::Raw(raw) if !preserve_whitespace && is_html_comment(raw) => Html::Empty,
+            other => other.clone(),
+        }
+    }
+
+    /// Copy of the tree in [`canonical_string`](Self::canonical_string) form.
+    fn canonicalized(&self, preserve_whitespace: bool) -> Html {
+        match self {
+            Html::Element(element) => {
+                let preserve = preserve_whitespace || preserves_whitespace(&element.tag);
+                let mut attributes = element.attributes.clone();
+                attributes.sort_by(|a, b| a.0.cmp(&b.0));
+                for (name, value) in &mut attributes {
+                    if name != "class" {
+                        continue;
+                    }
+                    let mut classes: Vec<String> = match value {
+                        HtmlAttribute::Value(v) => v.split_whitespace().map(String::from).collect(),
+                        HtmlAttribute::List(list) => list.clone(),
+                        HtmlAttribute::Boolean => continue,
+                    };
+                    classes.sort();
+                    *value = HtmlAttribute::Value(classes.join(" "));
+                }
+                Html::Element(HtmlElement {
+                    tag: element.tag.clone(),
+                    attributes,
+                    children: element
+                        .children
+                        .iter()
+                        .map(|child| child.canonicalized(preserve))
+                        .collect(),
+                    self_closing: element.self_closing,
+                    omit_if_empty: element.omit_if_empty,
+                })
+            }
+            Html::Fragment(nodes) => Html::Fragment(
+                nodes
+                    .iter()
+                    .map(|node| node.canonicalized(preserve_whitespace))
+                    .collect(),
+            ),
+            Html::Text(text) if !preserve_whitespace => Html::Text(collapse_whitespace(text)),
+            other => other.clone(),
+        }
+    }
+
+    /// Strong ETag for the rendered output — see [`etag`].
+    pub fn etag(&self) -> String {
+        etag(&self.render())
+    }
+
     /// Cheap lower-bound estimate of the rendered byte length, used to
     /// pre-size `String` buffers. Walks the tree once and sums a rough
     /// approximation of tag + attribute + child sizes. Underestimates mean
@@ -364,7 +795,7 @@ impl Html {
     pub fn render_to<W: Write>(&self, writer: &mut W) -> Result<()> {
         match self {
             Html::Text(text) => {
-                write!(writer, "{}", encode_text(text))
+                write!(writer, "{}", escape_text(text))
                     .map_err(|e| RuitlError::render(format!("Failed to write text: {}", e)))?;
             }
             Html::Raw(html) => {
@@ -395,7 +826,8 @@ impl Html {
             Html::Fragment(children) => {
                 children.is_empty() || children.iter().all(|c| c.is_empty())
             }
-            Html::Element(_) => false, // Elements are never considered empty
+            // Elements are never considered empty unless marked `omit_if_empty`
+            Html::Element(element) => element.is_omitted(),
         }
     }
 
@@ -418,9 +850,292 @@ impl Html {
             Html::Empty => String::new(),
         }
     }
+
+    /// Opt-in pass that runs [`sanitize_url`] over every URL-bearing
+    /// attribute (`href`, `src`, `action`, …) in the tree. Call it on
+    /// output that interpolates untrusted URLs before rendering.
+    pub fn sanitize_urls(self) -> Html {
+        match self {
+            Html::Element(element) => Html::Element(element.sanitize_urls()),
+            Html::Fragment(children) => {
+                Html::Fragment(children.into_iter().map(Html::sanitize_urls).collect())
+            }
+            other => other,
+        }
+    }
+
+    /// Rewrite every element in the tree with `f`, children before their
+    /// parent. Use it for tree-wide transforms such as
+    /// [`HtmlElement::set_tag`] renames.
+    pub fn map_elements<F>(self, mut f: F) -> Html
+    where
+        F: FnMut(HtmlElement) -> HtmlElement,
+    {
+        self.map_elements_with(&mut f)
+    }
+
+    fn map_elements_with<F>(self, f: &mut F) -> Html
+    where
+        F: FnMut(HtmlElement) -> HtmlElement,
+    {
+        match self {
+            Html::Element(mut element) => {
+                element.children = std::mem::take(&mut element.children)
+                    .into_iter()
+                    .map(|child| child.map_elements_with(f))
+                    .collect();
+                Html::Element(f(element))
+            }
+            Html::Fragment(children) => Html::Fragment(
+                children
+                    .into_iter()
+                    .map(|child| child.map_elements_with(f))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Prefix every root-relative URL attribute (`/static/app.css`) with the
+    /// path of `base_url`, for sites deployed below the domain root. See
+    /// [`rebase_url`] for which URLs are left alone.
+    pub fn rebase_urls(self, base_url: &str) -> Html {
+        match self {
+            Html::Element(element) => Html::Element(element.rebase_urls(base_url)),
+            Html::Fragment(children) => Html::Fragment(
+                children
+                    .into_iter()
+                    .map(|c| c.rebase_urls(base_url))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// Compare two trees structurally, ignoring differences that don't
+    /// change meaning: attribute order, runs of whitespace in text,
+    /// whitespace-only text nodes, fragment nesting and `Html::Empty`.
+    /// Intended for tests that would otherwise compare rendered strings.
+    pub fn normalized_eq(&self, other: &Html) -> bool {
+        self.normalized() == other.normalized()
+    }
+
+    /// Canonical form used by [`Html::normalized_eq`]: fragments flattened,
+    /// empties dropped, adjacent text merged with whitespace collapsed,
+    /// attributes sorted by name and list values joined into one string.
+    pub fn normalized(&self) -> Html {
+        let mut nodes = Vec::new();
+        self.normalize_into(&mut nodes);
+        collapse_text_nodes(&mut nodes);
+        match nodes.len() {
+            0 => Html::Empty,
+            1 => nodes.pop().unwrap(),
+            _ => Html::Fragment(nodes),
+        }
+    }
+
+    /// Flatten into `out`. Adjacent text is concatenated as written, so it
+    /// must go through [`collapse_text_nodes`] afterwards.
+    fn normalize_into(&self, out: &mut Vec<Html>) {
+        match self {
+            Html::Text(text) => {
+                if let Some(Html::Text(prev)) = out.last_mut() {
+                    prev.push_str(text);
+                } else {
+                    out.push(Html::Text(text.clone()));
+                }
+            }
+            Html::Raw(raw) => {
+                if !raw.trim().is_empty() {
+                    out.push(Html::Raw(raw.trim().to_string()));
+                }
+            }
+            Html::Element(element) if element.is_omitted() => {}
+            Html::Element(element) => {
+                let mut attributes: Vec<(String, HtmlAttribute)> = element
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| {
+                        let v = match v {
+                            HtmlAttribute::List(list) => HtmlAttribute::Value(list.join(" ")),
+                            other => other.clone(),
+                        };
+                        (k.clone(), v)
+                    })
+                    .collect();
+                attributes.sort_by(|a, b| a.0.cmp(&b.0));
+
+                let mut children = Vec::new();
+                for child in &element.children {
+                    child.normalize_into(&mut children);
+                }
+                collapse_text_nodes(&mut children);
+
+                out.push(Html::Element(HtmlElement {
+                    tag: element.tag.to_ascii_lowercase(),
+                    attributes,
+                    children,
+                    self_closing: element.is_self_closing(),
+                    omit_if_empty: element.omit_if_empty,
+                }));
+            }
+            Html::Fragment(children) => {
+                for child in children {
+                    child.normalize_into(out);
+                }
+            }
+            Html::Empty => {}
+        }
+    }
+
+    /// Render with one element per line, indented two spaces per level, for
+    /// reading generated markup. Elements holding a single line of text stay
+    /// on one line, and the contents of `<pre>`, `<textarea>`, `<script>` and
+    /// `<style>` are written byte-for-byte. Elsewhere text is trimmed and
+    /// whitespace-only text dropped, so inline content may not render the
+    /// same as [`render`](Self::render) — don't serve this form.
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        match self {
+            Html::Text(text) => {
+                let text = text.trim();
+                if !text.is_empty() {
+                    let _ = writeln!(out, "{}{}", indent, escape_text(text));
+                }
+            }
+            Html::Raw(raw) => {
+                let raw = raw.trim();
+                if !raw.is_empty() {
+                    let _ = writeln!(out, "{}{}", indent, raw);
+                }
+            }
+            Html::Element(element) if element.is_omitted() => {}
+            Html::Element(element) => {
+                let verbatim = preserves_whitespace(&element.tag);
+                let single_line = match element.children.as_slice() {
+                    [] => true,
+                    [Html::Text(text)] => !text.contains('\n'),
+                    _ => false,
+                };
+                if verbatim || single_line || element.is_self_closing() {
+                    out.push_str(&indent);
+                    let _ = element.render_to(out);
+                    out.push('\n');
+                    return;
+                }
+                let _ = write!(out, "{}<{}", indent, element.tag);
+                for (key, value) in &element.attributes {
+                    match value {
+                        HtmlAttribute::Boolean => {
+                            let _ = write!(out, " {}", key);
+                        }
+                        _ => {
+                            let _ = write!(out, " {}={}", key, value.render());
+                        }
+                    }
+                }
+                let _ = writeln!(out, ">");
+                for child in &element.children {
+                    child.write_pretty(out, depth + 1);
+                }
+                let _ = writeln!(out, "{}</{}>", indent, element.tag);
+            }
+            Html::Fragment(children) => {
+                for child in children {
+                    child.write_pretty(out, depth);
+                }
+            }
+            Html::Empty => {}
+        }
+    }
+
+    /// Indented, one-node-per-line dump of the tree — easier to read in a
+    /// failing assertion than `{:?}` or the rendered HTML.
+    pub fn debug_tree(&self) -> String {
+        let mut out = String::new();
+        self.write_debug_tree(&mut out, 0);
+        out
+    }
+
+    fn write_debug_tree(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        match self {
+            Html::Text(text) => {
+                let _ = writeln!(out, "{}text {:?}", indent, text);
+            }
+            Html::Raw(raw) => {
+                let _ = writeln!(out, "{}raw {:?}", indent, raw);
+            }
+            Html::Element(element) => {
+                let _ = write!(out, "{}<{}", indent, element.tag);
+                for (key, value) in &element.attributes {
+                    match value {
+                        HtmlAttribute::Boolean => {
+                            let _ = write!(out, " {}", key);
+                        }
+                        _ => {
+                            let _ = write!(out, " {}={}", key, value.render());
+                        }
+                    }
+                }
+                let _ = writeln!(out, ">");
+                for child in &element.children {
+                    child.write_debug_tree(out, depth + 1);
+                }
+            }
+            Html::Fragment(children) => {
+                let _ = writeln!(out, "{}fragment", indent);
+                for child in children {
+                    child.write_debug_tree(out, depth + 1);
+                }
+            }
+            Html::Empty => {
+                let _ = writeln!(out, "{}empty", indent);
+            }
+        }
+    }
 }
 
 impl HtmlElement {
+    /// Element-level [`Html::sanitize_urls`].
+    pub fn sanitize_urls(mut self) -> Self {
+        for (key, value) in &mut self.attributes {
+            if let HtmlAttribute::Value(url) = value {
+                if URL_ATTRIBUTES.contains(&key.to_ascii_lowercase().as_str()) {
+                    if let Cow::Owned(safe) = sanitize_url(url) {
+                        *url = safe;
+                    }
+                }
+            }
+        }
+        self.children = self.children.into_iter().map(Html::sanitize_urls).collect();
+        self
+    }
+
+    /// Element-level [`Html::rebase_urls`].
+    pub fn rebase_urls(mut self, base_url: &str) -> Self {
+        for (key, value) in &mut self.attributes {
+            if let HtmlAttribute::Value(url) = value {
+                let name = key.to_ascii_lowercase();
+                if let Cow::Owned(rebased) = rebase_attribute(&name, url, base_url) {
+                    *url = rebased;
+                }
+            }
+        }
+        self.children = self
+            .children
+            .into_iter()
+            .map(|c| c.rebase_urls(base_url))
+            .collect();
+        self
+    }
+
     /// Render the element to a string
     pub fn render(&self) -> String {
         let mut output = String::new();
@@ -430,6 +1145,10 @@ impl HtmlElement {
 
     /// Render the element to a writer
     pub fn render_to<W: Write>(&self, writer: &mut W) -> Result<()> {
+        if self.is_omitted() {
+            return Ok(());
+        }
+
         // Opening tag
         write!(writer, "<{}", self.tag)
             .map_err(|e| RuitlError::render(format!("Failed to write opening tag: {}", e)))?;
@@ -490,6 +1209,23 @@ impl From<HtmlElement> for Html {
     }
 }
 
+/// Collects into a single `Html::Fragment`. Nested fragments are spliced in
+/// and `Html::Empty` items dropped, so `items.map(render).collect::<Html>()`
+/// yields one flat fragment.
+impl FromIterator<Html> for Html {
+    fn from_iter<I: IntoIterator<Item = Html>>(iter: I) -> Self {
+        let mut nodes = Vec::new();
+        for node in iter {
+            match node {
+                Html::Fragment(children) => nodes.extend(children),
+                Html::Empty => {}
+                other => nodes.push(other),
+            }
+        }
+        Html::Fragment(nodes)
+    }
+}
+
 impl From<String> for Html {
     fn from(text: String) -> Self {
         Html::Text(text)
@@ -502,25 +1238,427 @@ impl From<&str> for Html {
     }
 }
 
-/// Check if a tag is a void element (self-closing)
-fn is_void_element(tag: &str) -> bool {
-    matches!(
-        tag.to_lowercase().as_str(),
-        "area"
-            | "base"
-            | "br"
-            | "col"
-            | "embed"
-            | "hr"
-            | "img"
-            | "input"
-            | "link"
-            | "meta"
-            | "param"
-            | "source"
-            | "track"
-            | "wbr"
-    )
+/// Attributes whose values are URLs the browser may navigate to or load.
+const URL_ATTRIBUTES: &[&str] = &[
+    "href",
+    "src",
+    "action",
+    "formaction",
+    "poster",
+    "cite",
+    "background",
+    "xlink:href",
+];
+
+/// Neutralize URLs with a script-capable scheme.
+///
+/// `javascript:`, `vbscript:` and `data:` URLs are replaced with `#`,
+/// except `data:image/*` (minus SVG, which can carry script). Relative
+/// URLs and other schemes pass through unchanged. Leading whitespace and
+/// embedded tabs/newlines are ignored when reading the scheme, matching
+/// how browsers parse `java\tscript:`. Returns `Cow::Owned` exactly when
+/// the URL was replaced.
+pub fn sanitize_url(url: &str) -> Cow<'_, str> {
+    let scheme: String = url
+        .trim_start()
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take(32)
+        .collect::<String>()
+        .to_ascii_lowercase();
+
+    let safe_image = scheme.starts_with("data:image/") && !scheme.starts_with("data:image/svg");
+    let dangerous = scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || (scheme.starts_with("data:") && !safe_image);
+
+    if dangerous {
+        Cow::Owned("#".to_string())
+    } else {
+        Cow::Borrowed(url)
+    }
+}
+
+/// Prefix a root-relative `url` with the path component of `base_url`
+/// (`https://host/docs/` → `/docs`).
+///
+/// Absolute and protocol-relative URLs (`https://…`, `//cdn/…`), relative
+/// paths, fragments and URLs that already carry the prefix are returned
+/// unchanged, so the pass is idempotent.
+pub fn rebase_url<'a>(url: &'a str, base_url: &str) -> Cow<'a, str> {
+    let prefix = base_path(base_url);
+    if prefix.is_empty() || !url.starts_with('/') || url.starts_with("//") {
+        return Cow::Borrowed(url);
+    }
+    match url.strip_prefix(prefix) {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '?', '#']) => Cow::Borrowed(url),
+        _ => Cow::Owned(format!("{}{}", prefix, url)),
+    }
+}
+
+/// [`rebase_url`] for the value of attribute `name` (lowercase): URL
+/// attributes as a whole, `srcset` per candidate, anything else unchanged.
+fn rebase_attribute<'a>(name: &str, value: &'a str, base_url: &str) -> Cow<'a, str> {
+    if name == "srcset" {
+        rebase_srcset(value, base_url)
+    } else if URL_ATTRIBUTES.contains(&name) {
+        rebase_url(value, base_url)
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// [`rebase_url`] for each candidate in a `srcset` value
+/// (`/a.png 1x, /b.png 2x`), keeping the descriptors.
+fn rebase_srcset<'a>(srcset: &'a str, base_url: &str) -> Cow<'a, str> {
+    let mut changed = false;
+    let candidates: Vec<Cow<'_, str>> = srcset
+        .split(',')
+        .map(|candidate| {
+            let rest = candidate.trim_start();
+            let lead = &candidate[..candidate.len() - rest.len()];
+            let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            match rebase_url(&rest[..url_end], base_url) {
+                Cow::Owned(url) => {
+                    changed = true;
+                    Cow::Owned(format!("{}{}{}", lead, url, &rest[url_end..]))
+                }
+                Cow::Borrowed(_) => Cow::Borrowed(candidate),
+            }
+        })
+        .collect();
+    if changed {
+        Cow::Owned(candidates.join(","))
+    } else {
+        Cow::Borrowed(srcset)
+    }
+}
+
+/// Elements whose content is text, not markup, so an `href=` inside them
+/// is not an attribute.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// Apply [`rebase_url`] to URL attributes in already-rendered HTML. Used by
+/// the static-site build, whose route renderers hand back strings.
+///
+/// Walks start tags attribute by attribute, so double-, single- and
+/// unquoted values, spaces around `=` and `srcset` are handled, while
+/// comments, end tags, look-alike names such as `data-href` and the
+/// contents of `<script>`/`<style>` are left alone.
+pub fn rebase_rendered_urls(html: &str, base_url: &str) -> String {
+    let bytes = html.as_bytes();
+    let len = bytes.len();
+    let is_delim = |b: u8| b.is_ascii_whitespace() || b == b'/' || b == b'>';
+    let skip_ws = |mut pos: usize| {
+        while pos < len && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        pos
+    };
+
+    let mut out = String::with_capacity(html.len());
+    let mut copied = 0;
+    let mut i = 0;
+    while let Some(offset) = html[i..].find('<') {
+        let start = i + offset;
+        if html[start..].starts_with("<!--") {
+            i = html[start..].find("-->").map_or(len, |end| start + end + 3);
+            continue;
+        }
+        if !bytes.get(start + 1).is_some_and(u8::is_ascii_alphabetic) {
+            // End tag, doctype or a stray `<` in text.
+            i = start + 1;
+            continue;
+        }
+
+        let mut pos = start + 1;
+        while pos < len && !is_delim(bytes[pos]) {
+            pos += 1;
+        }
+        let tag = html[start + 1..pos].to_ascii_lowercase();
+        loop {
+            pos = skip_ws(pos);
+            match bytes.get(pos) {
+                None => break,
+                Some(b'>') => {
+                    pos += 1;
+                    break;
+                }
+                Some(b'/') => {
+                    pos += 1;
+                    continue;
+                }
+                Some(_) => {}
+            }
+            let name_start = pos;
+            while pos < len && !is_delim(bytes[pos]) && bytes[pos] != b'=' {
+                pos += 1;
+            }
+            let name = html[name_start..pos].to_ascii_lowercase();
+            let eq = skip_ws(pos);
+            if bytes.get(eq) != Some(&b'=') {
+                // Attribute without a value.
+                continue;
+            }
+            let value_start = skip_ws(eq + 1);
+            let (value_start, value_end, next) = match bytes.get(value_start) {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let end = html[value_start + 1..]
+                        .find(quote as char)
+                        .map_or(len, |e| value_start + 1 + e);
+                    (value_start + 1, end, (end + 1).min(len))
+                }
+                _ => {
+                    let mut end = value_start;
+                    while end < len && !bytes[end].is_ascii_whitespace() && bytes[end] != b'>' {
+                        end += 1;
+                    }
+                    (value_start, end, end)
+                }
+            };
+            pos = next;
+            if let Cow::Owned(rebased) =
+                rebase_attribute(&name, &html[value_start..value_end], base_url)
+            {
+                out.push_str(&html[copied..value_start]);
+                out.push_str(&rebased);
+                copied = value_end;
+            }
+        }
+
+        i = pos;
+        if RAW_TEXT_ELEMENTS.contains(&tag.as_str()) {
+            let close = format!("</{}", tag);
+            i = html[pos..]
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or(len, |end| pos + end);
+        }
+    }
+    out.push_str(&html[copied..]);
+    out
+}
+
+/// Path part of a base URL without its trailing slash; `""` for the root.
+fn base_path(base_url: &str) -> &str {
+    let path = match base_url.find("://") {
+        Some(i) => {
+            let after_scheme = &base_url[i + 3..];
+            after_scheme.find('/').map_or("", |j| &after_scheme[j..])
+        }
+        None => base_url,
+    };
+    path.trim_end_matches('/')
+}
+
+/// Compute a strong ETag (quoted, per RFC 9110) for a rendered page.
+///
+/// MD5 of the body, hex-encoded. Not cryptographic — it only has to change
+/// when the content does. Pair with [`ComponentContext::is_not_modified`]
+/// to answer conditional GETs with a 304.
+///
+/// [`ComponentContext::is_not_modified`]: crate::component::ComponentContext::is_not_modified
+pub fn etag(rendered: &str) -> String {
+    format!("\"{:x}\"", md5::compute(rendered.as_bytes()))
+}
+
+/// Escape a string for use as text content.
+///
+/// Escapes all five XML-significant characters (`&`, `<`, `>`, `"`, `'`)
+/// so text and attribute output share one escaping rule. Borrows when
+/// nothing needs escaping.
+pub fn escape_text(input: &str) -> Cow<'_, str> {
+    escape_html(input)
+}
+
+/// Escape a string for use inside a double-quoted attribute value.
+///
+/// Same character set as [`escape_text`]; `'` becomes `&#39;` so the value
+/// is also safe if a caller re-quotes it with single quotes.
+pub fn escape_attribute(input: &str) -> Cow<'_, str> {
+    escape_html(input)
+}
+
+/// Make `input` safe as the body of an HTML comment (`<!-- … -->`).
+///
+/// Splits every `--` run (repeating until none is left, so `--->` can't
+/// survive as `- -->`) and pads a trailing `-`, which would otherwise join
+/// the closing `-->`. Used by the `@debug` directive.
+pub fn escape_comment(input: &str) -> Cow<'_, str> {
+    if !input.contains("--") && !input.ends_with('-') {
+        return Cow::Borrowed(input);
+    }
+    let mut out = input.to_string();
+    while out.contains("--") {
+        out = out.replace("--", "- -");
+    }
+    if out.ends_with('-') {
+        out.push(' ');
+    }
+    Cow::Owned(out)
+}
+
+/// Whether debug builds check `Html::raw` input for unbalanced tags.
+static RAW_MARKUP_CHECK: AtomicBool = AtomicBool::new(true);
+
+/// Turn the debug-build tag-balance check on `Html::raw` input on or off,
+/// e.g. for code that deliberately splits an element across raw chunks.
+/// Release builds never check.
+pub fn set_raw_markup_check(enabled: bool) {
+    RAW_MARKUP_CHECK.store(enabled, Ordering::Relaxed);
+}
+
+/// In debug builds with the check enabled, print a warning when `markup`
+/// has unbalanced tags. Returns the warning that was printed.
+fn warn_unbalanced_raw(markup: &str) -> Option<String> {
+    if !cfg!(debug_assertions) || !RAW_MARKUP_CHECK.load(Ordering::Relaxed) {
+        return None;
+    }
+    let problem = unbalanced_tag(markup)?;
+    let warning = format!(
+        "ruitl: warning: Html::raw markup looks unbalanced: {}",
+        problem
+    );
+    eprintln!("{}", warning);
+    Some(warning)
+}
+
+/// Elements whose end tag HTML lets authors omit; one left open, or closed
+/// implicitly by an ancestor's end tag, is not a balance problem.
+const OPTIONAL_END_TAGS: &[&str] = &[
+    "body", "caption", "colgroup", "dd", "dt", "head", "html", "li", "optgroup", "option", "p",
+    "rp", "rt", "tbody", "td", "tfoot", "th", "thead", "tr",
+];
+
+/// Byte offset of the `>` ending the tag that `rest` starts inside, skipping
+/// `>` in quoted attribute values (`<a title="1 > 0">`).
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut after_eq = false;
+    for (i, b) in rest.bytes().enumerate() {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None => match b {
+                b'>' => return Some(i),
+                b'"' | b'\'' if after_eq => quote = Some(b),
+                b'=' => after_eq = true,
+                _ if b.is_ascii_whitespace() => {}
+                _ => after_eq = false,
+            },
+        }
+    }
+    None
+}
+
+/// A cheap scan for the first tag-balance problem in `markup`: an
+/// unterminated tag or comment, a mismatched close, or an element left
+/// open. Doctypes, void elements and `<script>`/`<style>` bodies are
+/// skipped, a `<` not followed by a tag name is treated as text, and
+/// elements with optional end tags (`<li>`, `<p>`, `<td>`, …) may stay open.
+fn unbalanced_tag(markup: &str) -> Option<String> {
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = markup;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            let Some(end) = comment.find("-->") else {
+                return Some("unterminated comment `<!--`".to_string());
+            };
+            rest = &comment[end + 3..];
+            continue;
+        }
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?')) {
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            return Some(format!("unterminated tag `<{}`", rest.trim_end()));
+        };
+        let tag = &rest[..end];
+        rest = &rest[end + 1..];
+        if tag.starts_with(['!', '?']) {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            // An end tag implicitly closes open optional-end elements.
+            while open
+                .last()
+                .is_some_and(|top| *top != name && OPTIONAL_END_TAGS.contains(&top.as_str()))
+            {
+                open.pop();
+            }
+            match open.pop() {
+                Some(expected) if expected == name => continue,
+                Some(expected) => {
+                    return Some(format!("`</{}>` closes `<{}>`", name, expected));
+                }
+                None => return Some(format!("`</{}>` has no opening tag", name)),
+            }
+        }
+        let name = tag
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if tag.ends_with('/') || is_void_element(&name) {
+            continue;
+        }
+        if name == "script" || name == "style" {
+            let close = format!("</{}", name);
+            let Some(body_end) = rest.to_ascii_lowercase().find(&close) else {
+                return Some(format!("`<{}>` is never closed", name));
+            };
+            rest = &rest[body_end..];
+        }
+        open.push(name);
+    }
+    open.into_iter()
+        .rev()
+        .find(|name| !OPTIONAL_END_TAGS.contains(&name.as_str()))
+        .map(|name| format!("`<{}>` is never closed", name))
+}
+
+/// Second half of [`Html::normalized`]: collapse whitespace runs in each
+/// (already merged) text node, trim it, and drop it if nothing is left.
+fn collapse_text_nodes(nodes: &mut Vec<Html>) {
+    nodes.retain_mut(|node| match node {
+        Html::Text(text) => {
+            *text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            !text.is_empty()
+        }
+        _ => true,
+    });
+}
+
+/// Collapse each run of whitespace in `text` to one space, keeping a single
+/// leading/trailing space where there was any (it separates inline content).
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    if text.starts_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    for (i, word) in text.split_whitespace().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    if text.ends_with(char::is_whitespace) && out != " " {
+        out.push(' ');
+    }
+    out
+}
+
+/// Whether `raw` is a single HTML comment. Conditional comments
+/// (`<!--[if IE]>`) are not, since browsers act on them.
+fn is_html_comment(raw: &str) -> bool {
+    let raw = raw.trim();
+    raw.len() >= 7
+        && raw.starts_with("<!--")
+        && !raw.starts_with("<!--[")
+        && raw.ends_with("-->")
+        && raw[4..].find("-->") == Some(raw.len() - 7)
 }
 
 /// Convenient HTML builder functions
@@ -690,6 +1828,25 @@ where
     Html::fragment(children)
 }
 
+/// Join the class names whose flag is `true` with single spaces, for
+/// `class={classes(&[("btn", true), ("btn-lg", large)])}` in templates.
+/// Names are trimmed and empty ones skipped, so the result never has stray
+/// spaces; with no truthy names it is the empty string.
+pub fn classes(list: &[(&str, bool)]) -> String {
+    let mut out = String::new();
+    for (name, _) in list.iter().filter(|(_, on)| *on) {
+        let name = name.trim();
+        if name.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(name);
+    }
+    out
+}
+
 /// Extension methods used by generated code for conditional attributes.
 pub trait HtmlElementExt {
     fn attr_if(self, name: &str, condition: bool, value: &str) -> Self;
@@ -775,6 +1932,194 @@ mod tests {
         assert_eq!(String::from_utf8(chunks[0].clone()).unwrap(), e.render());
     }
 
+    #[test]
+    fn test_class_if() {
+        let on = a().class("tab").class_if("active", true);
+        assert_eq!(on.render(), r#"<a class="tab active"></a>"#);
+
+        let off = a().class("tab").class_if("active", false);
+        assert_eq!(off.render(), r#"<a class="tab"></a>"#);
+    }
+
+    #[test]
+    fn test_on_escapes_handler() {
+        let element = button().on("click", r#"alert("hi"); go('/')"#);
+        assert_eq!(
+            element.render(),
+            r#"<button onclick="alert(&quot;hi&quot;); go(&#39;/&#39;)"></button>"#
+        );
+
+        // Event names that happen to start with "on" still get the prefix.
+        let online = body().on("online", "sync()");
+        assert_eq!(online.render(), r#"<body ononline="sync()"></body>"#);
+    }
+
+    #[test]
+    fn test_pretty_string_keeps_preformatted_content() {
+        let code = "fn main() {\n    let less = a < b;\n}\n";
+        let page = Html::Element(
+            html().child(Html::Element(
+                body().child(Html::Element(
+                    div()
+                        .class("listing")
+                        .child(Html::Element(h1().text("Example")))
+                        .child(Html::text("\n   "))
+                        .child(Html::Element(HtmlElement::new("pre").text(code)))
+                        .child(Html::Element(
+                            HtmlElement::new("textarea").text("  a\n\n  b"),
+                        )),
+                )),
+            )),
+        );
+
+        let pretty = page.to_pretty_string();
+        assert_eq!(
+            pretty,
+            "<html>\n\
+             \x20 <body>\n\
+             \x20   <div class=\"listing\">\n\
+             \x20     <h1>Example</h1>\n\
+             \x20     <pre>fn main() {\n    let less = a &lt; b;\n}\n</pre>\n\
+             \x20     <textarea>  a\n\n  b</textarea>\n\
+             \x20   </div>\n\
+             \x20 </body>\n\
+             </html>\n"
+        );
+    }
+
+    #[test]
+    fn test_render_minified_collapses_whitespace_outside_pre() {
+        let page = Html::Element(
+            div()
+                .child(Html::text("\n    "))
+                .child(Html::raw("<!-- nav -->"))
+                .child(Html::Element(p().text("  Hello,\n   world  ")))
+                .child(Html::text("\n    "))
+                .child(Html::Element(
+                    HtmlElement::new("pre").text("  keep\n    this  "),
+                ))
+                .child(Html::raw("<!--[if IE]><p>old</p><![endif]-->"))
+                .child(Html::text("\n")),
+        );
+        let minified = page.render_minified();
+        #[cfg(not(feature = "minify"))]
+        assert_eq!(
+            minified,
+            "<div><p> Hello, world </p><pre>  keep\n    this  </pre><!--[if IE]><p>old</p><![endif]--></div>"
+        );
+        assert!(minified.contains("<pre>  keep\n    this  </pre>"));
+        assert!(!minified.contains("nav"));
+
+        assert!(is_html_comment("<!-- a -->"));
+        assert!(!is_html_comment("<!-- a --><b>x</b><!-- c -->"));
+        assert!(!is_html_comment("<!-->"));
+    }
+
+    #[test]
+    fn test_set_tag_renames_through_map_elements() {
+        let page = Html::Element(
+            div().child(Html::Element(
+                HtmlElement::new("center")
+                    .class("banner")
+                    .text("Welcome")
+                    .child(Html::Element(br())),
+            )),
+        );
+        let rewritten = page.map_elements(|mut element| {
+            match element.tag() {
+                "center" => element.set_tag("div"),
+                "br" => element.set_tag("span"),
+                _ => {}
+            }
+            element
+        });
+        assert_eq!(
+            rewritten.render(),
+            r#"<div><div class="banner">Welcome<span></span></div></div>"#
+        );
+
+        let mut rule = div().text("dropped");
+        rule.set_tag("hr");
+        assert_eq!(rule.tag(), "hr");
+        assert_eq!(rule.render(), "<hr />");
+    }
+
+    #[test]
+    fn test_omit_if_empty_drops_wrapper_without_content() {
+        let maybe = |show: bool| {
+            if show {
+                Html::text("Saved")
+            } else {
+                Html::Empty
+            }
+        };
+
+        let hidden = div()
+            .class("notice")
+            .omit_if_empty()
+            .child(maybe(false))
+            .child(Html::text(""));
+        assert_eq!(hidden.render(), "");
+        let page = Html::Element(main().child(Html::Element(hidden.clone())));
+        assert_eq!(page.render(), "<main></main>");
+
+        // Nested marked wrappers collapse together.
+        let outer = section().omit_if_empty().child(Html::Element(hidden));
+        assert_eq!(outer.render(), "");
+
+        let shown = div().class("notice").omit_if_empty().child(maybe(true));
+        assert_eq!(shown.render(), r#"<div class="notice">Saved</div>"#);
+
+        assert_eq!(div().child(maybe(false)).render(), "<div></div>");
+    }
+
+    #[test]
+    fn test_escape_comment_cannot_close_the_comment() {
+        for value in ["a--->b<script>", "x-", "----", "<!-- -->", "a--!>b"] {
+            let comment = format!("<!-- @debug: {} -->", escape_comment(value));
+            let body = &comment[4..comment.len() - 3];
+            assert!(!body.contains("--"), "{}", comment);
+            assert!(!body.ends_with('-'), "{}", comment);
+        }
+        assert_eq!(escape_comment("a--->b<script>"), "a- - ->b<script>");
+        assert!(matches!(escape_comment("a-b"), Cow::Borrowed("a-b")));
+    }
+
+    #[test]
+    fn test_classes_joins_truthy_names() {
+        assert_eq!(classes(&[("btn", false), ("disabled", false)]), "");
+        assert_eq!(
+            classes(&[
+                ("btn", true),
+                ("btn-lg", false),
+                (" disabled ", true),
+                ("", true)
+            ]),
+            "btn disabled"
+        );
+        assert_eq!(
+            classes(&[("btn", true), ("btn-lg", true), ("disabled", true)]),
+            "btn btn-lg disabled"
+        );
+    }
+
+    #[test]
+    fn test_data_and_aria_attributes() {
+        let element = button()
+            .data("user-id", "5")
+            .data("data-role", "save")
+            .aria("label", r#"Say "hi" <now>"#);
+        assert_eq!(
+            element.render(),
+            r#"<button data-user-id="5" data-role="save" aria-label="Say &quot;hi&quot; &lt;now&gt;"></button>"#
+        );
+
+        let mut map = std::collections::HashMap::new();
+        map.insert("count", "3");
+        let element = div().data_map(map);
+        assert_eq!(element.render(), r#"<div data-count="3"></div>"#);
+    }
+
     #[test]
     fn test_self_closing_element() {
         let element = img().attr("src", "test.jpg").attr("alt", "Test");
@@ -789,6 +2134,15 @@ mod tests {
         assert_eq!(html, r#"<input type="checkbox" checked />"#);
     }
 
+    #[test]
+    fn test_conditional_boolean_attribute() {
+        let on = button().bool_attr_if("disabled", true).text("Go");
+        assert_eq!(on.render(), "<button disabled>Go</button>");
+
+        let off = button().bool_attr_if("disabled", false).text("Go");
+        assert_eq!(off.render(), "<button>Go</button>");
+    }
+
     #[test]
     fn test_nested_elements() {
         let element = div()
@@ -811,6 +2165,133 @@ mod tests {
         assert!(!html.contains("<script>"));
     }
 
+    #[test]
+    fn test_escaping_all_five_entities() {
+        let input = r#"Tom & "Jerry" <'cat'>"#;
+        let expected = "Tom &amp; &quot;Jerry&quot; &lt;&#39;cat&#39;&gt;";
+
+        assert_eq!(Html::text(input).render(), expected);
+
+        let element = div().attr("title", input);
+        assert_eq!(
+            element.render(),
+            format!(r#"<div title="{}"></div>"#, expected)
+        );
+    }
+
+    #[test]
+    fn test_escaping_ampersand_first() {
+        // An already-escaped entity must be escaped again, not passed through.
+        assert_eq!(escape_text("&lt;"), "&amp;lt;");
+        assert_eq!(escape_attribute("&#39;"), "&amp;#39;");
+        assert!(matches!(escape_text("plain"), Cow::Borrowed("plain")));
+    }
+
+    #[test]
+    fn test_etag_tracks_content() {
+        let a = Html::Element(div().text("one"));
+        let b = Html::Element(div().text("two"));
+        assert_eq!(a.etag(), a.clone().etag());
+        assert_ne!(a.etag(), b.etag());
+        assert!(a.etag().starts_with('"') && a.etag().ends_with('"'));
+    }
+
+    #[test]
+    fn test_collect_into_fragment() {
+        let html: Html = ["a", "b", "c"].iter().map(|s| Html::text(*s)).collect();
+        assert_eq!(html.render(), "abc");
+
+        let nested: Html = vec![
+            Html::text("1"),
+            Html::fragment(vec![Html::text("2"), Html::text("3")]),
+            Html::Empty,
+            Html::Element(span().text("4")),
+        ]
+        .into_iter()
+        .collect();
+        let Html::Fragment(children) = &nested else {
+            panic!("expected fragment");
+        };
+        assert_eq!(children.len(), 4);
+        assert_eq!(nested.render(), "123<span>4</span>");
+    }
+
+    #[test]
+    fn test_normalized_eq_ignores_attribute_order_and_whitespace() {
+        let a = Html::Element(
+            div()
+                .attr("id", "main")
+                .class("card")
+                .child(Html::text("  Hello\n   world "))
+                .child(Html::Empty),
+        );
+        let b = Html::Element(
+            div()
+                .class("card")
+                .attr("id", "main")
+                .child(Html::fragment(vec![
+                    Html::text("Hello "),
+                    Html::Empty,
+                    Html::text("world"),
+                ])),
+        );
+        assert_ne!(a, b);
+        assert!(a.normalized_eq(&b));
+
+        // Adjacent text joins as rendered: no space is invented between them.
+        let joined = Html::fragment(vec![Html::text("Hello"), Html::text("world")]);
+        assert_eq!(joined.render(), "Helloworld");
+        assert!(!joined.normalized_eq(&Html::text("Hello world")));
+        assert!(joined.normalized_eq(&Html::text("Helloworld")));
+
+        let c = Html::Element(div().class("card").attr("id", "other"));
+        assert!(!a.normalized_eq(&c));
+    }
+
+    #[test]
+    fn test_debug_tree() {
+        let tree = Html::Element(ul().class("list").child(Html::Element(li().text("one"))));
+        assert_eq!(
+            tree.debug_tree(),
+            "<ul class=\"list\">\n  <li>\n    text \"one\"\n"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_url() {
+        assert_eq!(sanitize_url("javascript:alert(1)"), "#");
+        assert_eq!(sanitize_url("  JavaScript:alert(1)"), "#");
+        assert_eq!(sanitize_url("java\tscript:alert(1)"), "#");
+        assert_eq!(sanitize_url("data:text/html,<script>"), "#");
+        assert_eq!(sanitize_url("data:image/svg+xml,<svg>"), "#");
+        assert_eq!(
+            sanitize_url("data:image/png;base64,AAAA"),
+            "data:image/png;base64,AAAA"
+        );
+        assert_eq!(sanitize_url("/path?q=1"), "/path?q=1");
+        assert!(matches!(sanitize_url("/path"), Cow::Borrowed(_)));
+        assert!(matches!(sanitize_url("javascript:x"), Cow::Owned(_)));
+        assert_eq!(sanitize_url("https://example.com"), "https://example.com");
+    }
+
+    #[test]
+    fn test_sanitize_urls_in_tree() {
+        let tree = Html::Element(
+            div()
+                .child(Html::Element(
+                    a().attr("href", "javascript:steal()").text("x"),
+                ))
+                .child(Html::Element(a().attr("href", "/path").text("y")))
+                .child(Html::Element(img().attr("src", "data:text/html,hi"))),
+        )
+        .sanitize_urls();
+
+        assert_eq!(
+            tree.render(),
+            r##"<div><a href="#">x</a><a href="/path">y</a><img src="#" /></div>"##
+        );
+    }
+
     #[test]
     fn test_raw_html() {
         let element = div().raw("<em>emphasized</em>");
@@ -845,6 +2326,14 @@ mod tests {
         assert!(!is_void_element("span"));
     }
 
+    #[test]
+    fn test_whitespace_preserving_elements() {
+        assert!(preserves_whitespace("pre"));
+        assert!(preserves_whitespace("TEXTAREA"));
+        assert!(preserves_whitespace("script"));
+        assert!(!preserves_whitespace("div"));
+    }
+
     #[test]
     fn test_text_content() {
         let element = div()
@@ -856,6 +2345,264 @@ mod tests {
         assert_eq!(html.text_content(), "Hello world!");
     }
 
+    #[test]
+    fn test_attribute_queries() {
+        let element = a()
+            .attr("href", "/docs")
+            .bool_attr("download")
+            .class("btn")
+            .class("btn-primary");
+
+        assert_eq!(element.get_attr("href"), Some("/docs"));
+        assert_eq!(element.get_attr("download"), Some(""));
+        assert_eq!(element.get_attr("title"), None);
+        assert!(element.has_attr("download"));
+        assert!(!element.has_attr("title"));
+        assert!(element.has_class("btn-primary"));
+        assert!(element.has_class("btn"));
+        assert!(!element.has_class("btn-"));
+
+        let listed = div().classes(vec!["one", "two"]);
+        assert!(listed.has_class("two"));
+        assert_eq!(listed.get_attr("class"), None);
+    }
+
+    #[test]
+    fn test_rebase_urls_prefixes_root_relative_links() {
+        let base = "https://host/docs/";
+        let page = Html::fragment(vec![
+            Html::Element(HtmlElement::self_closing("link").attr("href", "/static/app.css")),
+            Html::Element(Html::element("script").attr("src", "https://cdn/x")),
+            Html::Element(a().attr("href", "/docs/guide").text("/not-a-link")),
+        ])
+        .rebase_urls(base);
+
+        let rendered = page.render();
+        assert!(rendered.contains(r#"href="/docs/static/app.css""#));
+        assert!(rendered.contains(r#"src="https://cdn/x""#));
+        assert!(rendered.contains(r#"href="/docs/guide""#));
+        assert!(rendered.contains(">/not-a-link<"));
+
+        let source = r#"<link href="/static/app.css" /><img alt="/x" src="//cdn/y" />"#;
+        assert_eq!(
+            rebase_rendered_urls(source, base),
+            r#"<link href="/docs/static/app.css" /><img alt="/x" src="//cdn/y" />"#
+        );
+    }
+
+    #[test]
+    fn test_rebase_rendered_urls_reads_attributes() {
+        let base = "/docs/";
+        let cases = [
+            ("<a href='/x'>", "<a href='/docs/x'>"),
+            ("<a href=/x>", "<a href=/docs/x>"),
+            ("<a href=/x/>", "<a href=/docs/x/>"),
+            (r#"<a href = "/x">"#, r#"<a href = "/docs/x">"#),
+            (r#"<A HREF="/x">"#, r#"<A HREF="/docs/x">"#),
+            (
+                r#"<img srcset="/a.png 1x, /b.png 2x, https://cdn/c.png 3x">"#,
+                r#"<img srcset="/docs/a.png 1x, /docs/b.png 2x, https://cdn/c.png 3x">"#,
+            ),
+            (
+                r#"<a data-href="/x" title="a > b" href="/y">"#,
+                r#"<a data-href="/x" title="a > b" href="/docs/y">"#,
+            ),
+            (
+                r#"<input disabled src="/i">"#,
+                r#"<input disabled src="/docs/i">"#,
+            ),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(rebase_rendered_urls(source, base), expected, "{}", source);
+        }
+
+        let untouched = [
+            r#"<script>var a = '<a href="/x">';</script>"#,
+            r#"<style>.x { background: url("/bg.png") } /* href="/y" */</style>"#,
+            r#"<!-- <a href="/x"> -->"#,
+            r#"<p>href="/x"</p>"#,
+        ];
+        for source in untouched {
+            assert_eq!(rebase_rendered_urls(source, base), source);
+        }
+
+        assert_eq!(
+            rebase_rendered_urls(r#"<script src="/app.js"></script><a href="/x">"#, base),
+            r#"<script src="/docs/app.js"></script><a href="/docs/x">"#
+        );
+    }
+
+    #[test]
+    fn test_rebase_url_edge_cases() {
+        assert_eq!(rebase_url("/a", "/docs"), "/docs/a");
+        assert_eq!(rebase_url("/docs", "/docs/"), "/docs");
+        assert_eq!(rebase_url("/docsite", "/docs/"), "/docs/docsite");
+        assert_eq!(rebase_url("relative.css", "/docs/"), "relative.css");
+        assert_eq!(rebase_url("/a", "https://host/"), "/a");
+    }
+
+    #[test]
+    fn test_render_pooled_matches_render_and_reuses_buffer() {
+        let big = Html::Element(div().children(
+            (0..200).map(|i| Html::Element(p().attr("data-i", i.to_string()).text("row & <cell>"))),
+        ));
+        let small = Html::Element(span().text("hi"));
+
+        let pooled = big.render_pooled();
+        assert_eq!(pooled.as_str(), big.render());
+        let warm_capacity = pooled.capacity();
+        drop(pooled);
+
+        let reused = small.render_pooled();
+        assert_eq!(&*reused, small.render());
+        assert!(
+            reused.capacity() >= warm_capacity,
+            "expected the pooled buffer ({} bytes) to be reused, got {}",
+            warm_capacity,
+            reused.capacity()
+        );
+        assert_eq!(reused.into_string(), "<span>hi</span>");
+    }
+
+    #[test]
+    fn test_child_insertion_order() {
+        let list = ul()
+            .child(Html::Element(li().text("2")))
+            .children(vec![
+                Html::Element(li().text("3")),
+                Html::Element(li().text("4")),
+            ])
+            .prepend_child(Html::Element(li().text("1")))
+            .child(Html::Element(li().text("5")));
+        assert_eq!(
+            list.render(),
+            "<ul><li>1</li><li>2</li><li>3</li><li>4</li><li>5</li></ul>"
+        );
+    }
+
+    #[test]
+    fn test_raw_markup_balance_check() {
+        assert_eq!(
+            unbalanced_tag("<div><p>Hi</p><br><img src=x /></div>"),
+            None
+        );
+        assert_eq!(unbalanced_tag("<!-- <div> --><p>a < b</p>"), None);
+        assert_eq!(unbalanced_tag("<script>if (a<b) {}</script>"), None);
+        assert_eq!(
+            unbalanced_tag("<div><span>oops</div>").as_deref(),
+            Some("`</div>` closes `<span>`")
+        );
+        assert_eq!(
+            unbalanced_tag("<div class=\"card\"").as_deref(),
+            Some("unterminated tag `<div class=\"card\"`")
+        );
+        assert_eq!(unbalanced_tag(r#"<a title="1 > 0" data-x='>'>x</a>"#), None);
+        assert_eq!(
+            unbalanced_tag("<ul><li>one<li>two</ul><p>para<table><tr><td>a<td>b</table>"),
+            None
+        );
+        assert_eq!(
+            unbalanced_tag("<ul><li><span>one</ul>").as_deref(),
+            Some("`</ul>` closes `<span>`")
+        );
+
+        // The switch is global, so only this test toggles it.
+        if cfg!(debug_assertions) {
+            let warning = warn_unbalanced_raw("<div><p>never closed").unwrap();
+            assert!(warning.contains("`<div>` is never closed"), "{}", warning);
+        }
+        set_raw_markup_check(false);
+        assert_eq!(warn_unbalanced_raw("<div>"), None);
+        set_raw_markup_check(true);
+        assert_eq!(warn_unbalanced_raw("<div></div>"), None);
+    }
+
+    #[test]
+    fn test_canonical_string_ignores_attribute_order_and_whitespace() {
+        let a = div()
+            .attr("id", "card")
+            .class("b a")
+            .attr("data-x", "1")
+            .child(Html::text("Hello,\n    world "))
+            .child(Html::Element(
+                HtmlElement::new("pre").child(Html::text("  keep   this")),
+            ));
+        let b = div()
+            .attr("data-x", "1")
+            .class("a b")
+            .attr("id", "card")
+            .child(Html::text("Hello, world "))
+            .child(Html::Element(
+                HtmlElement::new("pre").child(Html::text("  keep   this")),
+            ));
+
+        assert_ne!(a.render(), b.render());
+        let canonical = Html::Element(a).canonical_string();
+        assert_eq!(canonical, Html::Element(b).canonical_string());
+        assert_eq!(
+            canonical,
+            "<div class=\"a b\" data-x=\"1\" id=\"card\">Hello, world \
+             <pre>  keep   this</pre></div>"
+        );
+
+        let c = div().attr("id", "other").child(Html::text("Hello, world"));
+        assert_ne!(Html::Element(c).canonical_string(), canonical);
+    }
+
+    #[test]
+    fn test_to_dom_json() {
+        let link = Html::Element(a().attr("href", "/x").text("link"));
+        assert_eq!(
+            link.to_dom_json(),
+            serde_json::json!({
+                "tag": "a",
+                "attrs": { "href": "/x" },
+                "children": ["link"],
+            })
+        );
+
+        let page = Html::Fragment(vec![
+            Html::Element(
+                button()
+                    .class("btn")
+                    .class("primary")
+                    .attr("disabled", "")
+                    .bool_attr("hidden")
+                    .child(Html::Fragment(vec![Html::Empty, Html::raw("<b>!</b>")])),
+            ),
+            Html::text("a < b"),
+        ]);
+        assert_eq!(
+            page.to_dom_json(),
+            serde_json::json!([
+                {
+                    "tag": "button",
+                    "attrs": { "class": "btn primary", "disabled": "", "hidden": true },
+                    "children": [{ "raw": "<b>!</b>" }],
+                },
+                "a < b",
+            ])
+        );
+        assert_eq!(Html::Empty.to_dom_json(), serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_style_helpers_merge_declarations() {
+        let element = div().style("color", "red").style("margin", "0");
+        assert_eq!(
+            element.render(),
+            r#"<div style="color:red;margin:0"></div>"#
+        );
+
+        let merged = div()
+            .attr("style", "display: block;")
+            .styles(vec![("font-family", "\"Inter\""), ("gap", "1rem")]);
+        assert_eq!(
+            merged.render(),
+            r#"<div style="display: block;font-family:&quot;Inter&quot;;gap:1rem"></div>"#
+        );
+    }
+
     #[test]
     fn test_empty_html() {
         assert!(Html::empty().is_empty());
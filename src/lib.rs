@@ -40,6 +40,13 @@
 //! println!("{}", html.render());
 //! ```
 
+/// `axum` adapters: `IntoResponse` for `Html`/`RuitlError` and a
+/// `ComponentContext` extractor. Off by default.
+#[cfg(feature = "axum")]
+pub mod axum;
+/// Pick precompressed `.br` / `.gz` siblings of static files by
+/// `Accept-Encoding`.
+pub mod assets;
 pub mod build;
 pub mod cli;
 pub mod component;
@@ -66,15 +73,21 @@ pub use ruitl_compiler::parser;
 pub use ruitl_compiler::codegen;
 
 // Re-export commonly used items
-pub use component::{Component, ComponentContext, ComponentProps, EmptyProps};
+pub use component::{
+    CancellationToken, Component, ComponentContext, ComponentProps, EmptyProps, Experiment,
+    SecurityContext,
+};
 pub use error::{Result, RuitlError};
 pub use html::{Html, HtmlAttribute, HtmlElement};
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::component::{Component, ComponentContext, ComponentProps, EmptyProps};
+    pub use crate::component::{
+        CancellationToken, Component, ComponentContext, ComponentProps, EmptyProps, Experiment,
+        SecurityContext,
+    };
     pub use crate::error::{Result, RuitlError};
-    pub use crate::html::{Html, HtmlAttribute, HtmlElement};
+    pub use crate::html::{classes, Html, HtmlAttribute, HtmlElement};
 
     // Common std imports for templates
     pub use std::collections::HashMap;
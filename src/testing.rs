@@ -27,6 +27,7 @@
 use crate::component::{Component, ComponentContext};
 use crate::error::Result;
 use crate::html::Html;
+use std::fmt;
 
 /// A thin wrapper around a `Component` that manages `ComponentContext`
 /// creation and error propagation for tests.
@@ -124,6 +125,198 @@ impl HtmlAssertion {
     }
 }
 
+/// An element placed where the HTML content model doesn't allow it, found
+/// by [`Html::validate_nesting`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestingWarning {
+    /// Tags from the root down to the offending element, e.g. `p > div`
+    pub path: String,
+    /// Which rule was broken
+    pub message: String,
+}
+
+impl fmt::Display for NestingWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Elements that end an open `<p>`, so can't appear inside one.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Elements that end the search for an open `<p>`: the HTML parser only
+/// closes a `<p>` in "button scope", which these bound.
+const P_SCOPE_BOUNDARIES: &[&str] = &[
+    "applet", "button", "caption", "html", "marquee", "math", "object", "svg", "table", "td",
+    "template", "th",
+];
+
+/// Elements that can't be nested inside `<a>` or `<button>`.
+const INTERACTIVE_ELEMENTS: &[&str] = &["a", "button", "input", "select", "textarea"];
+
+/// Allowed parents for elements that only make sense in one context.
+const REQUIRED_PARENTS: &[(&str, &[&str])] = &[
+    ("li", &["ul", "ol", "menu"]),
+    ("dt", &["dl", "div"]),
+    ("dd", &["dl", "div"]),
+    ("tr", &["table", "thead", "tbody", "tfoot"]),
+    ("td", &["tr"]),
+    ("th", &["tr"]),
+    ("thead", &["table"]),
+    ("tbody", &["table"]),
+    ("tfoot", &["table"]),
+    ("caption", &["table"]),
+    ("option", &["select", "datalist", "optgroup"]),
+];
+
+/// Allowed children for elements with a fixed content model.
+/// `script` and `template` are allowed everywhere.
+const ALLOWED_CHILDREN: &[(&str, &[&str])] = &[
+    ("ul", &["li"]),
+    ("ol", &["li"]),
+    (
+        "table",
+        &["caption", "colgroup", "thead", "tbody", "tfoot", "tr"],
+    ),
+    ("thead", &["tr"]),
+    ("tbody", &["tr"]),
+    ("tfoot", &["tr"]),
+    ("tr", &["td", "th"]),
+];
+
+impl Html {
+    /// Check the tree against a subset of the HTML content model: no block
+    /// elements inside `<p>`, `<li>` only in lists, table parts only in
+    /// their table elements, no interactive elements inside `<a>` or
+    /// `<button>`, and so on. Meant for tests that catch codegen producing
+    /// markup a browser would silently re-parent. Raw HTML is not inspected.
+    pub fn validate_nesting(&self) -> Vec<NestingWarning> {
+        let mut warnings = Vec::new();
+        check_nesting(self, &mut Vec::new(), &mut warnings);
+        warnings
+    }
+}
+
+fn check_nesting(node: &Html, ancestors: &mut Vec<String>, out: &mut Vec<NestingWarning>) {
+    let path = |ancestors: &[String], tag: &str| {
+        let mut parts: Vec<&str> = ancestors.iter().map(String::as_str).collect();
+        parts.push(tag);
+        parts.join(" > ")
+    };
+    match node {
+        Html::Fragment(nodes) => {
+            for child in nodes {
+                check_nesting(child, ancestors, out);
+            }
+        }
+        Html::Text(text) if !text.trim().is_empty() => {
+            if let Some(parent) = ancestors.last() {
+                if ALLOWED_CHILDREN.iter().any(|(tag, _)| tag == parent) {
+                    out.push(NestingWarning {
+                        path: path(ancestors, "#text"),
+                        message: format!("text is not allowed directly inside <{}>", parent),
+                    });
+                }
+            }
+        }
+        Html::Element(element) => {
+            let tag = element.tag.to_ascii_lowercase();
+            let parent = ancestors.last().map(String::as_str);
+            let mut warn = |message: String| {
+                out.push(NestingWarning {
+                    path: path(ancestors, &tag),
+                    message,
+                })
+            };
+
+            if BLOCK_ELEMENTS.contains(&tag.as_str()) && in_open_p(ancestors) {
+                warn(format!("<{}> is not allowed inside <p>", tag));
+            }
+            if INTERACTIVE_ELEMENTS.contains(&tag.as_str()) {
+                if let Some(outer) = ancestors.iter().rev().find(|a| *a == "a" || *a == "button") {
+                    warn(format!("<{}> is not allowed inside <{}>", tag, outer));
+                }
+            }
+            // At the root the parent is up to the caller, e.g. a component
+            // that renders the `<li>`s of someone else's list.
+            if let (Some(parent), Some((_, parents))) =
+                (parent, REQUIRED_PARENTS.iter().find(|(t, _)| *t == tag))
+            {
+                if !parents.contains(&parent) {
+                    warn(format!(
+                        "<{}> must be inside <{}>",
+                        tag,
+                        parents.join(">, <")
+                    ));
+                }
+            }
+            if let Some(parent) = parent {
+                if let Some((_, children)) = ALLOWED_CHILDREN.iter().find(|(t, _)| *t == parent) {
+                    if !children.contains(&tag.as_str()) && tag != "script" && tag != "template" {
+                        warn(format!(
+                            "<{}> is not allowed directly inside <{}>",
+                            tag, parent
+                        ));
+                    }
+                }
+            }
+
+            ancestors.push(tag);
+            for child in &element.children {
+                check_nesting(child, ancestors, out);
+            }
+            ancestors.pop();
+        }
+        _ => {}
+    }
+}
+
+/// Whether a block element under `ancestors` would land in an open `<p>`:
+/// the nearest `p` reached through phrasing elements such as `<span>` or
+/// `<a>`, stopping at a block or scope boundary.
+fn in_open_p(ancestors: &[String]) -> bool {
+    for ancestor in ancestors.iter().rev() {
+        if ancestor == "p" {
+            return true;
+        }
+        if BLOCK_ELEMENTS.contains(&ancestor.as_str())
+            || P_SCOPE_BOUNDARIES.contains(&ancestor.as_str())
+        {
+            return false;
+        }
+    }
+    false
+}
+
 /// Convenience: assert a rendered `Html` (or `&String`/`&str`) contains a
 /// substring. Works on anything that derefs to `str` via `.to_string()` or
 /// `Html::render()` — whichever the caller hands in.
@@ -192,7 +385,7 @@ impl Renderable for String {
 /// Grab-bag re-export so test bodies can `use ruitl::testing::prelude::*;`
 /// and not think about individual symbol paths.
 pub mod prelude {
-    pub use super::{ComponentTestHarness, HtmlAssertion};
+    pub use super::{ComponentTestHarness, HtmlAssertion, NestingWarning};
     pub use crate::component::{Component, ComponentContext, ComponentProps};
     pub use crate::html::Html;
     // Macros from this crate are accessible via the root of the caller's
@@ -218,11 +411,7 @@ mod tests {
     impl Component for Echo {
         type Props = EchoProps;
 
-        fn render(
-            &self,
-            props: &Self::Props,
-            _ctx: &ComponentContext,
-        ) -> Result<Html> {
+        fn render(&self, props: &Self::Props, _ctx: &ComponentContext) -> Result<Html> {
             Ok(Html::Element(div().class("echo").text(&props.msg)))
         }
     }
@@ -262,6 +451,70 @@ mod tests {
         HtmlAssertion::new(&tree).element_count("li", 3);
     }
 
+    #[test]
+    fn validate_nesting_reports_content_model_violations() {
+        let el = |tag: &str| HtmlElement::new(tag);
+        let bad_p = Html::Element(el("p").child(Html::Element(el("div"))));
+        assert_eq!(
+            bad_p.validate_nesting(),
+            vec![NestingWarning {
+                path: "p > div".into(),
+                message: "<div> is not allowed inside <p>".into(),
+            }]
+        );
+
+        let deep_p = Html::Element(el("p").child(Html::Element(
+            el("span").child(Html::Element(el("a").child(Html::Element(el("div"))))),
+        )));
+        assert_eq!(
+            deep_p.validate_nesting(),
+            vec![NestingWarning {
+                path: "p > span > a > div".into(),
+                message: "<div> is not allowed inside <p>".into(),
+            }]
+        );
+        let scoped = Html::Element(el("p").child(Html::Element(
+            el("button").child(Html::Element(el("span").child(Html::Element(el("div"))))),
+        )));
+        assert!(scoped.validate_nesting().is_empty());
+
+        // A component may render list items for a caller's list.
+        let items = Html::fragment(vec![
+            Html::Element(el("li").text("a")),
+            Html::Element(el("li").text("b")),
+        ]);
+        assert!(items.validate_nesting().is_empty());
+
+        let ok = Html::Element(el("ul").child(Html::fragment(vec![
+            Html::Element(el("li").child(Html::Element(el("p").text("a")))),
+            Html::Element(el("li").text("b")),
+        ])));
+        assert!(ok.validate_nesting().is_empty());
+
+        let bad = Html::Element(
+            div()
+                .child(Html::Element(el("li").text("stray")))
+                .child(Html::Element(el("table").child(Html::Element(el("td")))))
+                .child(Html::Element(el("a").child(Html::Element(
+                    el("span").child(Html::Element(el("button"))),
+                )))),
+        );
+        let messages: Vec<String> = bad
+            .validate_nesting()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "div > li: <li> must be inside <ul>, <ol>, <menu>",
+                "div > table > td: <td> must be inside <tr>",
+                "div > table > td: <td> is not allowed directly inside <table>",
+                "div > a > span > button: <button> is not allowed inside <a>",
+            ]
+        );
+    }
+
     #[test]
     #[should_panic(expected = "expected rendered HTML to contain `nope`")]
     fn assertion_contains_panics_on_miss() {
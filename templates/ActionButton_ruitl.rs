@@ -0,0 +1,85 @@
+// ruitl-hash: 6f4a163a080166ceb4615efcb54d86c0
+// ruitl-components: ActionButton
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
+use ruitl::html::*;
+use ruitl::prelude::*;
+#[derive(Debug, Clone)]
+pub struct ActionButtonProps {
+    pub label: String,
+    pub large: bool,
+    pub disabled: bool,
+}
+impl ComponentProps for ActionButtonProps {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+#[allow(dead_code)]
+impl ActionButtonProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> ActionButtonPropsBuilder {
+        ActionButtonPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct ActionButtonPropsBuilder {
+    label: Option<String>,
+    large: Option<bool>,
+    disabled: Option<bool>,
+}
+#[allow(dead_code)]
+impl ActionButtonPropsBuilder {
+    pub fn label(mut self, value: impl Into<String>) -> Self {
+        self.label = Some(value.into());
+        self
+    }
+    pub fn large(mut self, value: impl Into<bool>) -> Self {
+        self.large = Some(value.into());
+        self
+    }
+    pub fn disabled(mut self, value: impl Into<bool>) -> Self {
+        self.disabled = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<ActionButtonProps> {
+        let props = ActionButtonProps {
+            label: self
+                .label
+                .ok_or_else(|| RuitlError::validation("missing required prop `label`"))?,
+            large: self.large.unwrap_or(false),
+            disabled: self.disabled.unwrap_or(false),
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
+#[derive(Debug)]
+pub struct ActionButton;
+impl Component for ActionButton {
+    type Props = ActionButtonProps;
+    #[allow(unused_variables)]
+    fn render(&self, props: &Self::Props, _context: &ComponentContext) -> Result<Html> {
+        let label = &props.label;
+        let large = props.large;
+        let disabled = props.disabled;
+        Ok(Html::Element(
+            HtmlElement::new("button")
+                .attr(
+                    "class",
+                    &format!(
+                        "{}",
+                        classes(&[("btn", true), ("btn-lg", large), ("disabled", disabled)])
+                    ),
+                )
+                .attr("type", "button")
+                .child(Html::text(&format!("{}", label))),
+        ))
+    }
+}
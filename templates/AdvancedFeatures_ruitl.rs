@@ -1,4 +1,11 @@
-// ruitl-hash: bfcc3ddc9ed4e1b0be69920a271b4d14
+// ruitl-hash: 12d37a32c14b2792bad34f4cd85960fc
+// ruitl-components: AdvancedFeatures
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -14,6 +21,61 @@ impl ComponentProps for AdvancedFeaturesProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl AdvancedFeaturesProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> AdvancedFeaturesPropsBuilder {
+        AdvancedFeaturesPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct AdvancedFeaturesPropsBuilder {
+    title: Option<String>,
+    items: Option<Vec<String>>,
+    show_header: Option<bool>,
+    user_role: Option<String>,
+    count: Option<usize>,
+}
+#[allow(dead_code)]
+impl AdvancedFeaturesPropsBuilder {
+    pub fn title(mut self, value: impl Into<String>) -> Self {
+        self.title = Some(value.into());
+        self
+    }
+    pub fn items(mut self, value: impl Into<Vec<String>>) -> Self {
+        self.items = Some(value.into());
+        self
+    }
+    pub fn show_header(mut self, value: impl Into<bool>) -> Self {
+        self.show_header = Some(value.into());
+        self
+    }
+    pub fn user_role(mut self, value: impl Into<String>) -> Self {
+        self.user_role = Some(value.into());
+        self
+    }
+    pub fn count(mut self, value: impl Into<usize>) -> Self {
+        self.count = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<AdvancedFeaturesProps> {
+        let props = AdvancedFeaturesProps {
+            title: self
+                .title
+                .ok_or_else(|| RuitlError::validation("missing required prop `title`"))?,
+            items: self
+                .items
+                .ok_or_else(|| RuitlError::validation("missing required prop `items`"))?,
+            show_header: self.show_header.unwrap_or(true),
+            user_role: self.user_role.unwrap_or_else(|| "guest".to_string()),
+            count: self.count.unwrap_or(0),
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct AdvancedFeatures;
 impl Component for AdvancedFeatures {
@@ -70,7 +132,7 @@ impl Component for AdvancedFeatures {
                                                 items
                                                     .into_iter()
                                                     .map(|item| {
-                                                        Html::Element(
+                                                        Ok(Html::Element(
                                                             HtmlElement::new("li")
                                                                 .attr("class", "item")
                                                                 .child(Html::Element(
@@ -94,9 +156,9 @@ impl Component for AdvancedFeatures {
                                                                 } else {
                                                                     Html::Empty
                                                                 }),
-                                                        )
+                                                        ))
                                                     })
-                                                    .collect::<Vec<_>>(),
+                                                    .collect::<Result<Vec<_>>>()?,
                                             ),
                                         ),
                                     )
@@ -1,4 +1,11 @@
-// ruitl-hash: 37803d88c19264fa99b0ea8126bb6cc5
+// ruitl-hash: 1411834b55cf04bc1b971b7a2619e315
+// ruitl-components: Button
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -11,6 +18,41 @@ impl ComponentProps for ButtonProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl ButtonProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> ButtonPropsBuilder {
+        ButtonPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct ButtonPropsBuilder {
+    text: Option<String>,
+    variant: Option<String>,
+}
+#[allow(dead_code)]
+impl ButtonPropsBuilder {
+    pub fn text(mut self, value: impl Into<String>) -> Self {
+        self.text = Some(value.into());
+        self
+    }
+    pub fn variant(mut self, value: impl Into<String>) -> Self {
+        self.variant = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<ButtonProps> {
+        let props = ButtonProps {
+            text: self
+                .text
+                .ok_or_else(|| RuitlError::validation("missing required prop `text`"))?,
+            variant: self.variant.unwrap_or_else(|| "primary".to_string()),
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct Button;
 impl Component for Button {
@@ -0,0 +1,72 @@
+// ruitl-hash: 17662d5f2b514ee13a96f0a6149bcaa2
+// ruitl-components: FeatureNav
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
+use ruitl::html::*;
+use ruitl::prelude::*;
+#[derive(Debug, Clone)]
+pub struct FeatureNavProps {
+    pub brand: String,
+}
+impl ComponentProps for FeatureNavProps {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+#[allow(dead_code)]
+impl FeatureNavProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> FeatureNavPropsBuilder {
+        FeatureNavPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct FeatureNavPropsBuilder {
+    brand: Option<String>,
+}
+#[allow(dead_code)]
+impl FeatureNavPropsBuilder {
+    pub fn brand(mut self, value: impl Into<String>) -> Self {
+        self.brand = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<FeatureNavProps> {
+        let props = FeatureNavProps {
+            brand: self
+                .brand
+                .ok_or_else(|| RuitlError::validation("missing required prop `brand`"))?,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
+#[derive(Debug)]
+pub struct FeatureNav;
+impl Component for FeatureNav {
+    type Props = FeatureNavProps;
+    #[allow(unused_variables)]
+    fn render(&self, props: &Self::Props, context: &ComponentContext) -> Result<Html> {
+        let brand = &props.brand;
+        Ok(Html::Element(HtmlElement::new("nav").child(
+            if context.feature("new_nav") {
+                Html::Element(
+                    HtmlElement::new("div")
+                        .attr("class", "nav nav-new")
+                        .child(Html::text(&format!("{}", brand))),
+                )
+            } else {
+                Html::Element(
+                    HtmlElement::new("div")
+                        .attr("class", "nav")
+                        .child(Html::text(&format!("{}", brand))),
+                )
+            },
+        )))
+    }
+}
@@ -1,4 +1,11 @@
-// ruitl-hash: 40a04a273b2682ce507572d2800345cc
+// ruitl-hash: 3567646b144893b29a9616ffb29b24d8
+// ruitl-components: Hello
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -10,6 +17,35 @@ impl ComponentProps for HelloProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl HelloProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> HelloPropsBuilder {
+        HelloPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct HelloPropsBuilder {
+    name: Option<String>,
+}
+#[allow(dead_code)]
+impl HelloPropsBuilder {
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<HelloProps> {
+        let props = HelloProps {
+            name: self
+                .name
+                .ok_or_else(|| RuitlError::validation("missing required prop `name`"))?,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct Hello;
 impl Component for Hello {
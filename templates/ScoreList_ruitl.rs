@@ -0,0 +1,74 @@
+// ruitl-hash: f8f65e324a2d80b112addfdfcb84b9f4
+// ruitl-components: ScoreList
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
+use ruitl::html::*;
+use ruitl::prelude::*;
+#[derive(Debug, Clone)]
+pub struct ScoreListProps {
+    pub scores: Vec<String>,
+}
+impl ComponentProps for ScoreListProps {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+#[allow(dead_code)]
+impl ScoreListProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> ScoreListPropsBuilder {
+        ScoreListPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct ScoreListPropsBuilder {
+    scores: Option<Vec<String>>,
+}
+#[allow(dead_code)]
+impl ScoreListPropsBuilder {
+    pub fn scores(mut self, value: impl Into<Vec<String>>) -> Self {
+        self.scores = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<ScoreListProps> {
+        let props = ScoreListProps {
+            scores: self
+                .scores
+                .ok_or_else(|| RuitlError::validation("missing required prop `scores`"))?,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
+#[derive(Debug)]
+pub struct ScoreList;
+impl Component for ScoreList {
+    type Props = ScoreListProps;
+    #[allow(unused_variables)]
+    fn render(&self, props: &Self::Props, _context: &ComponentContext) -> Result<Html> {
+        let scores = &props.scores;
+        Ok(Html::Element(
+            HtmlElement::new("ol").child(Html::fragment(
+                scores
+                    .into_iter()
+                    .map(|raw| {
+                        Ok(Html::Element(
+                            HtmlElement::new("li").child(Html::text(&format!(
+                                "{}",
+                                raw.trim()
+                                    .parse::<u32>()
+                                    .map_err(|e| RuitlError::validation(e.to_string()))?
+                            ))),
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            )),
+        ))
+    }
+}
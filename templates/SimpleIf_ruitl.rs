@@ -1,4 +1,11 @@
-// ruitl-hash: a2ce6497990853e7004e0e4c0cd5ecad
+// ruitl-hash: 210c2d9d1aeb22b8bfe781e11ca5b344
+// ruitl-components: SimpleIf
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -10,6 +17,35 @@ impl ComponentProps for SimpleIfProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl SimpleIfProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> SimpleIfPropsBuilder {
+        SimpleIfPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct SimpleIfPropsBuilder {
+    show_message: Option<bool>,
+}
+#[allow(dead_code)]
+impl SimpleIfPropsBuilder {
+    pub fn show_message(mut self, value: impl Into<bool>) -> Self {
+        self.show_message = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<SimpleIfProps> {
+        let props = SimpleIfProps {
+            show_message: self
+                .show_message
+                .ok_or_else(|| RuitlError::validation("missing required prop `show_message`"))?,
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct SimpleIf;
 impl Component for SimpleIf {
@@ -1,4 +1,11 @@
-// ruitl-hash: 86ac47347c69d21b6fb17aa310d84f0f
+// ruitl-hash: 7f8b00af794e123c5db314c765c49458
+// ruitl-components: UserCard
+#![allow(
+    clippy::useless_format,
+    clippy::format_in_format_args,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrows_for_generic_args
+)]
 use ruitl::html::*;
 use ruitl::prelude::*;
 #[derive(Debug, Clone)]
@@ -12,6 +19,49 @@ impl ComponentProps for UserCardProps {
         Ok(())
     }
 }
+#[allow(dead_code)]
+impl UserCardProps {
+    #[doc = r" Start building props; unset fields take their declared defaults."]
+    pub fn builder() -> UserCardPropsBuilder {
+        UserCardPropsBuilder::default()
+    }
+}
+#[allow(dead_code)]
+#[derive(Debug, Clone, Default)]
+pub struct UserCardPropsBuilder {
+    name: Option<String>,
+    email: Option<String>,
+    role: Option<String>,
+}
+#[allow(dead_code)]
+impl UserCardPropsBuilder {
+    pub fn name(mut self, value: impl Into<String>) -> Self {
+        self.name = Some(value.into());
+        self
+    }
+    pub fn email(mut self, value: impl Into<String>) -> Self {
+        self.email = Some(value.into());
+        self
+    }
+    pub fn role(mut self, value: impl Into<String>) -> Self {
+        self.role = Some(value.into());
+        self
+    }
+    #[doc = r" Fill in defaults, check required props are set, then `validate()`."]
+    pub fn build(self) -> Result<UserCardProps> {
+        let props = UserCardProps {
+            name: self
+                .name
+                .ok_or_else(|| RuitlError::validation("missing required prop `name`"))?,
+            email: self
+                .email
+                .ok_or_else(|| RuitlError::validation("missing required prop `email`"))?,
+            role: self.role.unwrap_or_else(|| "user".to_string()),
+        };
+        props.validate()?;
+        Ok(props)
+    }
+}
 #[derive(Debug)]
 pub struct UserCard;
 impl Component for UserCard {
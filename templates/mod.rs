@@ -1,13 +1,32 @@
 // @generated by ruitl_compiler — do not edit. Regenerated on each compile.
 
+#[allow(non_snake_case)] pub mod ActionButton_ruitl;
 #[allow(non_snake_case)] pub mod AdvancedFeatures_ruitl;
 #[allow(non_snake_case)] pub mod Button_ruitl;
+#[allow(non_snake_case)] pub mod FeatureNav_ruitl;
 #[allow(non_snake_case)] pub mod Hello_ruitl;
+#[allow(non_snake_case)] pub mod ScoreList_ruitl;
 #[allow(non_snake_case)] pub mod SimpleIf_ruitl;
 #[allow(non_snake_case)] pub mod UserCard_ruitl;
 
+#[allow(unused_imports)] pub use ActionButton_ruitl::*;
 #[allow(unused_imports)] pub use AdvancedFeatures_ruitl::*;
 #[allow(unused_imports)] pub use Button_ruitl::*;
+#[allow(unused_imports)] pub use FeatureNav_ruitl::*;
 #[allow(unused_imports)] pub use Hello_ruitl::*;
+#[allow(unused_imports)] pub use ScoreList_ruitl::*;
 #[allow(unused_imports)] pub use SimpleIf_ruitl::*;
 #[allow(unused_imports)] pub use UserCard_ruitl::*;
+
+/// Register every component compiled from this directory, keyed by name.
+#[allow(dead_code, unused_variables)]
+pub fn register_all(renderer: &mut ruitl::component::ComponentRenderer) {
+    renderer.register("ActionButton", ActionButton_ruitl::ActionButton);
+    renderer.register("AdvancedFeatures", AdvancedFeatures_ruitl::AdvancedFeatures);
+    renderer.register("Button", Button_ruitl::Button);
+    renderer.register("FeatureNav", FeatureNav_ruitl::FeatureNav);
+    renderer.register("Hello", Hello_ruitl::Hello);
+    renderer.register("ScoreList", ScoreList_ruitl::ScoreList);
+    renderer.register("SimpleIf", SimpleIf_ruitl::SimpleIf);
+    renderer.register("UserCard", UserCard_ruitl::UserCard);
+}
@@ -3,7 +3,7 @@
 //! This test verifies that the CLI-generated components compile correctly
 //! and function as expected with proper variable access and advanced features.
 
-use ruitl::component::{Component, ComponentContext};
+use ruitl::component::{Component, ComponentContext, ComponentRenderer};
 
 // Include the generated components from their sibling *_ruitl.rs files.
 #[path = "../templates/mod.rs"]
@@ -137,6 +137,16 @@ fn test_generated_button_component() {
     assert!(html_string.contains("btn-primary"));
 }
 
+#[test]
+fn test_generated_props_builder_applies_defaults() {
+    let props = ButtonProps::builder().text("Hi").build().unwrap();
+    assert_eq!(props.text, "Hi");
+    assert_eq!(props.variant, "primary");
+
+    let err = ButtonProps::builder().build().unwrap_err();
+    assert!(err.to_string().contains("missing required prop `text`"));
+}
+
 #[test]
 fn test_generated_user_card_component() {
     let context = ComponentContext::new();
@@ -315,3 +325,90 @@ fn test_empty_items_handling() {
     assert!(html_string.contains("Welcome"));
     assert!(!html_string.contains("<ul")); // No list should be rendered
 }
+
+#[test]
+fn test_generated_feature_flag_gates_markup() {
+    let props = FeatureNavProps {
+        brand: "Acme".to_string(),
+    };
+
+    let default_html = FeatureNav
+        .render(&props, &ComponentContext::new())
+        .unwrap()
+        .to_string();
+    assert!(default_html.contains(r#"class="nav""#));
+    assert!(!default_html.contains("nav-new"));
+
+    let context = ComponentContext::new().with_feature("new_nav", true);
+    let flagged_html = FeatureNav.render(&props, &context).unwrap().to_string();
+    assert!(flagged_html.contains(r#"class="nav nav-new""#));
+    assert!(flagged_html.contains("Acme"));
+}
+
+#[test]
+fn test_generated_loop_body_propagates_errors_with_question_mark() {
+    let context = ComponentContext::new();
+
+    let ok = ScoreList
+        .render(
+            &ScoreListProps {
+                scores: vec!["3".to_string(), " 10 ".to_string()],
+            },
+            &context,
+        )
+        .unwrap();
+    assert_eq!(ok.to_string(), "<ol><li>3</li><li>10</li></ol>");
+
+    let err = ScoreList
+        .render(
+            &ScoreListProps {
+                scores: vec!["3".to_string(), "ten".to_string()],
+            },
+            &context,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("invalid digit"), "{}", err);
+}
+
+#[test]
+fn test_generated_class_list_from_classes_helper() {
+    let context = ComponentContext::new();
+
+    let plain = ActionButtonProps::builder().label("Save").build().unwrap();
+    let html = ActionButton.render(&plain, &context).unwrap().to_string();
+    assert_eq!(html, r#"<button class="btn" type="button">Save</button>"#);
+
+    let props = ActionButtonProps::builder()
+        .label("Save")
+        .large(true)
+        .disabled(true)
+        .build()
+        .unwrap();
+    let html = ActionButton.render(&props, &context).unwrap().to_string();
+    assert!(html.contains(r#"class="btn btn-lg disabled""#), "{}", html);
+}
+
+#[test]
+fn test_register_all_registers_every_compiled_component() {
+    let mut renderer = ComponentRenderer::new();
+    generated_components::register_all(&mut renderer);
+
+    let names = renderer.registry().names();
+    for expected in [
+        "ActionButton",
+        "AdvancedFeatures",
+        "Button",
+        "FeatureNav",
+        "Hello",
+        "ScoreList",
+        "SimpleIf",
+        "UserCard",
+    ] {
+        assert!(
+            names.contains(&expected),
+            "{} missing from {:?}",
+            expected,
+            names
+        );
+    }
+}
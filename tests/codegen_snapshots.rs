@@ -50,3 +50,5 @@ snap!(match_arms);
 snap!(composition);
 snap!(generics);
 snap!(children);
+snap!(class_directive);
+snap!(annotations);
@@ -0,0 +1,43 @@
+//! Build the crate with optional features switched off, so code that only
+//! compiles with `dev` (hotwatch) stays behind its `#[cfg]`.
+//!
+//! Spawns a nested `cargo check` into its own target directory, so it's
+//! `#[ignore]`d by default. Opt in with:
+//!
+//!   RUITL_TEST_FEATURES=1 cargo test --test feature_matrix_test -- --ignored
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn check_with_features(features: &str) {
+    let repo = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let out = Command::new(env!("CARGO"))
+        .args([
+            "check",
+            "--lib",
+            "--bins",
+            "--no-default-features",
+            "--features",
+        ])
+        .arg(features)
+        .current_dir(&repo)
+        .env("CARGO_TARGET_DIR", repo.join("target/feature-matrix"))
+        .env("RUSTFLAGS", "-D warnings")
+        .output()
+        .expect("run cargo check");
+    assert!(
+        out.status.success(),
+        "cargo check --no-default-features --features {} failed:\n{}",
+        features,
+        String::from_utf8_lossy(&out.stderr)
+    );
+}
+
+#[test]
+#[ignore = "slow; opt in via RUITL_TEST_FEATURES=1 cargo test -- --ignored"]
+fn builds_without_dev_feature() {
+    if std::env::var("RUITL_TEST_FEATURES").is_err() {
+        return;
+    }
+    check_with_features("server,static");
+}
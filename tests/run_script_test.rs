@@ -0,0 +1,68 @@
+//! `ruitl run <name>` executes the matching `[scripts]` entry from
+//! `ruitl.toml`.
+
+use std::process::Command;
+use tempfile::TempDir;
+
+fn write_config(dir: &std::path::Path) -> std::path::PathBuf {
+    let config = dir.join("ruitl.toml");
+    std::fs::write(
+        &config,
+        r#"[project]
+name = "scripts_probe"
+version = "0.1.0"
+authors = []
+
+[build]
+template_dir = "templates"
+out_dir = "generated"
+src_dir = "src"
+
+[scripts]
+touch = "echo ran > touched.txt"
+"#,
+    )
+    .unwrap();
+    config
+}
+
+#[cfg(unix)]
+#[test]
+fn run_executes_named_script_from_config_dir() {
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path());
+
+    let out = Command::new(env!("CARGO_BIN_EXE_ruitl"))
+        .arg("run")
+        .arg("touch")
+        .arg("--config")
+        .arg(&config)
+        .output()
+        .expect("spawn ruitl run");
+    assert!(
+        out.status.success(),
+        "ruitl run failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    let touched = std::fs::read_to_string(dir.path().join("touched.txt")).unwrap();
+    assert_eq!(touched.trim(), "ran");
+}
+
+#[test]
+fn run_rejects_unknown_script() {
+    let dir = TempDir::new().unwrap();
+    let config = write_config(dir.path());
+
+    let out = Command::new(env!("CARGO_BIN_EXE_ruitl"))
+        .arg("run")
+        .arg("deploy")
+        .arg("--config")
+        .arg(&config)
+        .output()
+        .expect("spawn ruitl run");
+    assert!(!out.status.success());
+    let stderr = String::from_utf8_lossy(&out.stderr);
+    assert!(stderr.contains("unknown script `deploy`"), "{}", stderr);
+    assert!(stderr.contains("available: touch"), "{}", stderr);
+}
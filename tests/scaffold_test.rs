@@ -86,3 +86,49 @@ fn scaffolded_project_builds_warning_free() {
         warnings.join("\n")
     );
 }
+
+#[test]
+fn scaffold_dry_run_lists_paths_without_writing() {
+    let dir = TempDir::new().unwrap();
+    let out = Command::new(env!("CARGO_BIN_EXE_ruitl"))
+        .arg("scaffold")
+        .arg("--name")
+        .arg("dry_probe")
+        .arg("--target")
+        .arg(dir.path())
+        .arg("--with-server")
+        .arg("--dry-run")
+        .output()
+        .expect("spawn ruitl scaffold");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(
+        out.status.success(),
+        "scaffold --dry-run failed: {}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+
+    assert_eq!(
+        std::fs::read_dir(dir.path()).unwrap().count(),
+        0,
+        "dry run must not write anything"
+    );
+    for expected in [
+        "  src/\n",
+        "  src/handlers/\n",
+        "  templates/Button.ruitl (",
+        "  templates/Button_ruitl.rs (generated)\n",
+        "  templates/mod.rs (generated)\n",
+        "  Cargo.toml (",
+        "  ruitl.toml (",
+        "  src/lib.rs (",
+        "  src/main.rs (",
+        "  static/css/styles.css (",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "missing {:?} in:\n{}",
+            expected,
+            stdout
+        );
+    }
+}
@@ -9,6 +9,7 @@
 use ruitl::codegen::CodeGenerator;
 use ruitl::parser::{AttributeValue, RuitlParser, TemplateAst};
 use std::fs;
+use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
 /// `proc_macro2::TokenStream::to_string()` emits spaces around every piece of
@@ -35,6 +36,22 @@ macro_rules! assert_contains_norm {
     }};
 }
 
+/// Write `<name>.ruitl` into `dir`: a component with one `name: String`
+/// prop and a template rendering it in a `<p>`. Returns the source path.
+fn write_template(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(format!("{}.ruitl", name));
+    fs::write(
+        &path,
+        format!(
+            "component {0} {{ props {{ name: String }} }}\n\
+             ruitl {0}(name: String) {{ <p>{{name}}</p> }}",
+            name
+        ),
+    )
+    .expect("Failed to write template");
+    path
+}
+
 #[test]
 fn test_simple_component_compilation() {
     let template = r#"
@@ -563,6 +580,41 @@ ruitl Card(props: CardProps) {
     }
 }
 
+#[test]
+fn test_string_literal_param_default_binds_owned_string() {
+    let template = r#"
+component Badge {
+    props {
+        label: String,
+        tone: String?,
+        size: u32?,
+    }
+}
+
+ruitl Badge(label: String, tone: String = "info", size: u32 = 2) {
+    <span class={tone} data-size={size.to_string()}>{label}</span>
+}
+"#;
+
+    let mut parser = RuitlParser::new(template.to_string());
+    let ast = parser.parse().expect("Failed to parse template");
+    let mut generator = CodeGenerator::new(ast);
+    let generated = generator
+        .generate()
+        .expect("Failed to generate code")
+        .to_string();
+
+    assert_contains_norm!(
+        generated,
+        r#"let tone: String = props.tone.clone().unwrap_or_else(|| "info".to_string());"#
+    );
+    assert_contains_norm!(
+        generated,
+        "let size: u32 = props.size.clone().unwrap_or(2);"
+    );
+    syn::parse_file(&generated).expect("generated code must be valid Rust");
+}
+
 #[test]
 fn test_prop_validation() {
     let template = r#"
@@ -677,3 +729,286 @@ ruitl RawContent(props: RawContentProps) {
     assert_contains_norm!(code_str, "props.safe_content");
     assert_contains_norm!(code_str, "props.html_content");
 }
+
+#[test]
+fn test_empty_template_file_is_skipped() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+
+    write_template(dir, "Hello");
+    fs::write(dir.join("Empty.ruitl"), "  \n\n").expect("Failed to write Empty template");
+
+    let report = ruitl_compiler::compile_dir_sibling_report(dir).expect("compile dir");
+
+    assert_eq!(report.outputs, vec![dir.join("Hello_ruitl.rs")]);
+    assert_eq!(report.skipped, vec![dir.join("Empty.ruitl")]);
+    assert!(!dir.join("Empty_ruitl.rs").exists());
+
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(mod_rs.contains("pub mod Hello_ruitl;"));
+    assert!(mod_rs.contains("renderer.register(\"Hello\", Hello_ruitl::Hello);"));
+    assert!(!mod_rs.contains("Empty"));
+
+    let err = ruitl_compiler::compile_file_sibling(&dir.join("Empty.ruitl")).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("no component or template definitions"));
+}
+
+#[test]
+fn test_emptied_source_drops_its_sibling_and_mod_entry() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    write_template(dir, "Hello");
+    write_template(dir, "Bye");
+
+    ruitl_compiler::compile_dir_sibling_report(dir).expect("compile dir");
+    let hello = fs::read_to_string(dir.join("Hello_ruitl.rs")).expect("Hello output");
+    assert_eq!(hello.lines().nth(1), Some("// ruitl-components: Hello"));
+    assert!(fs::read_to_string(dir.join("mod.rs"))
+        .expect("mod.rs written")
+        .contains("renderer.register(\"Bye\", Bye_ruitl::Bye);"));
+
+    fs::write(dir.join("Bye.ruitl"), "// nothing here yet\n").expect("Failed to empty Bye");
+    let report = ruitl_compiler::compile_dir_sibling_report(dir).expect("recompile dir");
+    assert_eq!(report.skipped, vec![dir.join("Bye.ruitl")]);
+    assert!(!dir.join("Bye_ruitl.rs").exists());
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(mod_rs.contains("pub mod Hello_ruitl;"), "{}", mod_rs);
+    assert!(!mod_rs.contains("Bye"), "{}", mod_rs);
+}
+
+#[test]
+fn test_configured_header_and_edition() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    write_template(dir, "Hello");
+    let hash_line = || {
+        let sibling = fs::read_to_string(dir.join("Hello_ruitl.rs")).expect("sibling written");
+        sibling.lines().next().unwrap_or_default().to_string()
+    };
+
+    ruitl_compiler::compile_dir_sibling_report_with(dir, &Default::default()).expect("compile dir");
+    let default_hash = hash_line();
+
+    let mut config = ruitl::config::RuitlConfig::default();
+    config.build.generated_header = Some("Owned by the build.\nRun `ruitl compile`.".into());
+    config.build.edition = Some("2018".into());
+    let options = config.build.compile_options();
+
+    ruitl_compiler::compile_dir_sibling_report_with(dir, &options).expect("compile dir");
+    // Changing either option must regenerate the siblings.
+    assert_ne!(hash_line(), default_hash);
+
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(mod_rs.starts_with(&format!(
+        "// {} Owned by the build.\n// Run `ruitl compile`.\n\n",
+        ruitl_compiler::GENERATED_MARKER
+    )));
+
+    let args = options.rustfmt_args();
+    let edition = args
+        .iter()
+        .position(|a| a == "--edition")
+        .map(|i| &args[i + 1]);
+    assert_eq!(edition.map(String::as_str), Some("2018"));
+
+    let defaults = ruitl::config::RuitlConfig::default()
+        .build
+        .compile_options();
+    assert_eq!(defaults, ruitl_compiler::CompileOptions::default());
+}
+
+#[test]
+fn test_fold_static_is_opt_in() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let source = dir.join("Banner.ruitl");
+    fs::write(
+        &source,
+        "component Banner { props { title: String } }\n\
+         ruitl Banner(title: String) { <header><p class=\"lead\">Hi & bye</p><h1>{title}</h1></header> }",
+    )
+    .expect("Failed to write Banner template");
+    let output = dir.join("Banner_ruitl.rs");
+
+    let mut config = ruitl::config::RuitlConfig::default();
+    ruitl_compiler::compile_file_sibling_with(&source, &config.build.compile_options())
+        .expect("compile");
+    let plain = fs::read_to_string(&output).expect("read output");
+    assert!(!plain.contains("Html::Raw("), "{}", plain);
+
+    config.build.fold_static = true;
+    ruitl_compiler::compile_file_sibling_with(&source, &config.build.compile_options())
+        .expect("compile folded");
+    let folded = fs::read_to_string(&output).expect("read output");
+    assert_contains_norm!(
+        folded,
+        r#"Html::Raw("<p class=\"lead\">Hi &amp; bye</p>".to_string())"#
+    );
+    assert_ne!(plain.lines().next(), folded.lines().next());
+}
+
+#[test]
+fn test_inline_handler_lint_reports_through_dir_compile() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("Counter.ruitl"),
+        "component Counter { props { count: u32 } }\n\
+         ruitl Counter(count: u32) { <button onclick=\"increment()\">{count}</button> }",
+    )
+    .expect("Failed to write Counter template");
+
+    let mut config = ruitl::config::RuitlConfig::default();
+    config.build.lint_inline_handlers = true;
+    let report =
+        ruitl_compiler::compile_dir_sibling_report_with(dir, &config.build.compile_options())
+            .expect("compile dir");
+    assert_eq!(report.warnings.len(), 1, "{:?}", report.warnings);
+    assert!(report.warnings[0].contains("`onclick` on `<button>`"));
+}
+
+#[test]
+fn test_write_mod_file_tracks_added_and_removed_templates() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    let options = ruitl_compiler::CompileOptions::default();
+    let hello = write_template(dir, "Hello");
+    ruitl_compiler::compile_dir_sibling_report_with(dir, &options).expect("compile dir");
+
+    // A template added while watching is compiled on its own, then listed.
+    let bye = write_template(dir, "Bye");
+    let out = ruitl_compiler::compile_file_sibling_with(&bye, &options).expect("compile Bye");
+    assert_eq!(out, ruitl_compiler::sibling_path(&bye).unwrap());
+    ruitl_compiler::write_mod_file(dir, &options).expect("write mod.rs");
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(mod_rs.contains("pub mod Bye_ruitl;"));
+    assert!(mod_rs.contains("pub mod Hello_ruitl;"));
+
+    // Removing a template and its output drops it from mod.rs.
+    fs::remove_file(&hello).unwrap();
+    fs::remove_file(dir.join("Hello_ruitl.rs")).unwrap();
+    ruitl_compiler::write_mod_file(dir, &options).expect("write mod.rs");
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(!mod_rs.contains("Hello"));
+
+    // With nothing left, the generated mod.rs goes too.
+    fs::remove_file(&bye).unwrap();
+    fs::remove_file(&out).unwrap();
+    ruitl_compiler::write_mod_file(dir, &options).expect("write mod.rs");
+    assert!(!dir.join("mod.rs").exists());
+}
+
+#[test]
+fn test_clean_removes_only_generated_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    write_template(dir, "Hello");
+    ruitl_compiler::compile_dir_sibling(dir).expect("compile dir");
+    // Hand-written files with generated-looking names are left alone.
+    fs::create_dir(dir.join("nested")).unwrap();
+    fs::write(dir.join("nested/mod.rs"), "pub mod helpers;\n").unwrap();
+    fs::write(dir.join("Manual_ruitl.rs"), "pub fn manual() {}\n").unwrap();
+
+    assert_eq!(
+        ruitl_compiler::generated_files(dir),
+        vec![dir.join("Hello_ruitl.rs"), dir.join("mod.rs")]
+    );
+
+    let clean = |dry_run: bool| {
+        let mut cmd = std::process::Command::new(env!("CARGO_BIN_EXE_ruitl"));
+        cmd.arg("clean").arg("--src-dir").arg(dir);
+        if dry_run {
+            cmd.arg("--dry-run");
+        }
+        let out = cmd.output().expect("spawn ruitl clean");
+        assert!(
+            out.status.success(),
+            "ruitl clean failed: {}",
+            String::from_utf8_lossy(&out.stderr)
+        );
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    let listed = clean(true);
+    assert!(listed.contains("  Hello_ruitl.rs\n"), "{}", listed);
+    assert!(listed.contains("  mod.rs\n"), "{}", listed);
+    assert!(!listed.contains("Manual"), "{}", listed);
+    assert!(dir.join("Hello_ruitl.rs").exists());
+
+    let removed = clean(false);
+    assert!(
+        removed.contains("Removed 2 generated file(s)"),
+        "{}",
+        removed
+    );
+    assert!(!dir.join("Hello_ruitl.rs").exists());
+    assert!(!dir.join("mod.rs").exists());
+    assert!(dir.join("Hello.ruitl").exists());
+    assert!(dir.join("Manual_ruitl.rs").exists());
+    assert!(dir.join("nested/mod.rs").exists());
+}
+
+#[test]
+fn test_mod_file_with_props_only_and_template_only_sources() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("Shared.ruitl"),
+        "component Shared { props { label: String } }",
+    )
+    .expect("Failed to write Shared template");
+
+    // A props-only file is re-exported by glob, so mod.rs never names a type
+    // it didn't produce, and there is no component to register.
+    ruitl_compiler::compile_dir_sibling(dir).expect("compile dir");
+    let generated = fs::read_to_string(dir.join("Shared_ruitl.rs")).unwrap();
+    assert!(generated.contains("pub struct SharedProps"));
+    let mod_rs = fs::read_to_string(dir.join("mod.rs")).expect("mod.rs written");
+    assert!(mod_rs.contains("#[allow(unused_imports)] pub use Shared_ruitl::*;"));
+    assert!(!mod_rs.contains("renderer.register("));
+
+    // A template without its `component` block is rejected up front rather
+    // than producing a module with unresolved `OnlyProps` references.
+    let only = dir.join("Only.ruitl");
+    fs::write(&only, "ruitl Only(name: String) { <p>{name}</p> }").unwrap();
+    let err = ruitl_compiler::compile_file_sibling(&only).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("No component definition found for template 'Only'"));
+    assert!(!dir.join("Only_ruitl.rs").exists());
+}
+
+#[test]
+fn test_check_reports_each_file_without_writing() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dir = temp_dir.path();
+    write_template(dir, "Good");
+    fs::write(
+        dir.join("Bad.ruitl"),
+        "component Bad { props { name: String } }\n\
+         ruitl Bad(name: String) {\n    <p>{name}</p\n}",
+    )
+    .expect("Failed to write Bad template");
+
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_ruitl"))
+        .arg("check")
+        .arg("--src-dir")
+        .arg(dir)
+        .output()
+        .expect("spawn ruitl check");
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    assert!(!out.status.success(), "{}", stdout);
+    assert!(stdout.contains(&format!("OK {}", dir.join("Good.ruitl").display())));
+    assert!(stdout.contains(&format!("FAIL {}", dir.join("Bad.ruitl").display())));
+    assert!(stdout.contains("at line 3, column"), "{}", stdout);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("1 of 2 template(s) failed"));
+
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name())
+        .collect();
+    entries.sort();
+    assert_eq!(entries, ["Bad.ruitl", "Good.ruitl"]);
+}
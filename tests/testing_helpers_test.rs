@@ -26,7 +26,7 @@ impl Component for Greet {
     fn render(&self, props: &Self::Props, _ctx: &ComponentContext) -> Result<Html> {
         use ruitl::html::{h1, HtmlElement};
         Ok(Html::Element(HtmlElement::new("section").child(
-            Html::Element(h1().text(&format!("Hello, {}!", props.name))),
+            Html::Element(h1().text(format!("Hello, {}!", props.name))),
         )))
     }
 }